@@ -1,6 +1,8 @@
 use crate::engine::{EvaluateEngine, GameState, SearchEngine, TimeInfo};
-use chess::{Board, BoardStatus, ChessMove};
+use chess::{Board, BoardStatus, ChessMove, Square};
 use std::str::FromStr;
+use std::time::Instant;
+use vampirc_uci::Duration;
 
 /// Represents the outcome of a chess game
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -106,6 +108,50 @@ impl std::fmt::Display for Pgn {
     }
 }
 
+/// A clock-based time control for `play_match`, in the same shape UCI's `go wtime/btime/
+/// winc/binc`/`movetime` exposes.
+#[derive(Debug, Clone, Copy)]
+pub enum TimeControl {
+    /// Both sides start with `initial` time and gain `increment` after every move they play.
+    Clock { initial: Duration, increment: Duration },
+    /// A fixed budget per move, independent of any running clock.
+    MoveTime(Duration),
+}
+
+/// Renders a `TimeControl` as a PGN `[TimeControl "..."]` tag value: `seconds+increment`
+/// for a clock, or `seconds/move` for a fixed per-move budget.
+fn time_control_tag(time_control: TimeControl) -> String {
+    match time_control {
+        TimeControl::Clock { initial, increment } => {
+            format!("{}+{}", initial.num_seconds(), increment.num_seconds())
+        }
+        TimeControl::MoveTime(move_time) => format!("{}/move", move_time.num_seconds()),
+    }
+}
+
+/// Eval-threshold adjudication, so self-play games for training-data generation don't have
+/// to grind out every already-decided game to `max_moves`. Evals are read from the side-to-
+/// move's perspective (as `EvaluateEngine::evaluate` reports them) and normalized to White's
+/// perspective before being compared against these thresholds.
+#[derive(Debug, Clone, Copy)]
+pub struct Adjudication {
+    /// Centipawn eval (White's perspective) a side must stay at or above (White) / at or
+    /// below its negation (Black) for `win_plies` consecutive plies to be adjudicated the
+    /// winner.
+    pub win_threshold: i16,
+    pub win_plies: u32,
+    /// Once `draw_min_ply` plies have been played, adjudicate a draw if |eval| stays at or
+    /// under this many centipawns for `draw_plies` consecutive plies.
+    pub draw_threshold: i16,
+    pub draw_plies: u32,
+    pub draw_min_ply: usize,
+}
+
+/// One training position sampled from a `play_match` game: the FEN of the position, its
+/// eval (from the side to move's perspective) at the time it was played, and the game's
+/// eventual result.
+pub type Sample = (String, i16, GameResult);
+
 /// Plays a match between two engines from a given FEN position.
 ///
 /// # Arguments
@@ -113,9 +159,17 @@ impl std::fmt::Display for Pgn {
 /// * `black_engine` - The engine playing as black
 /// * `fen` - The FEN string representing the starting position
 /// * `max_moves` - Maximum number of moves before declaring a draw (optional)
+/// * `time_control` - Clock or per-move time budget; `None` means untimed (searches never
+///   lose on time)
+/// * `evaluator` - When supplied, evaluates the position after every ply to record a
+///   `Sample` and (if `adjudication` is also supplied) to drive early stopping
+/// * `adjudication` - Eval-threshold rules for ending a decided game early; ignored
+///   without an `evaluator`
 ///
 /// # Returns
-/// A tuple containing the game result, final game state, and PGN of the game
+/// A tuple of the game result, final game state, PGN of the game, the wall-clock time each
+/// engine spent on each of its moves (in ply order, for auditing time management), and the
+/// `Sample`s collected if an `evaluator` was supplied.
 ///
 /// # Example
 /// ```
@@ -126,11 +180,14 @@ impl std::fmt::Display for Pgn {
 /// let mut white_engine = Negamax::new();
 /// let mut black_engine = Negamax::new();
 ///
-/// let (result, final_state, pgn) = play_match::<PstEval>(
+/// let (result, final_state, pgn, move_times, samples) = play_match::<PstEval>(
 ///     &mut white_engine,
 ///     &mut black_engine,
 ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
 ///     Some(100), // Max 100 moves
+///     None, // Untimed
+///     None, // No eval tracking
+///     None, // No adjudication
 /// ).unwrap();
 ///
 /// println!("Game result: {}", result);
@@ -142,7 +199,10 @@ pub fn play_match<E: EvaluateEngine>(
     black_engine: &mut impl SearchEngine<E>,
     fen: &str,
     max_moves: Option<usize>,
-) -> Result<(GameResult, GameState, Pgn), String> {
+    time_control: Option<TimeControl>,
+    mut evaluator: Option<&mut E>,
+    adjudication: Option<Adjudication>,
+) -> Result<(GameResult, GameState, Pgn, Vec<Duration>, Vec<Sample>), String> {
     // Parse the FEN and create game state
     let board = Board::from_str(fen).map_err(|e| format!("Invalid FEN: {}", e))?;
     let mut state = GameState::from_board(board);
@@ -154,13 +214,44 @@ pub fn play_match<E: EvaluateEngine>(
     let max_moves = max_moves.unwrap_or(200);
     let mut move_count = 0;
     let mut move_list: Vec<String> = Vec::new();
+    let mut move_times: Vec<Duration> = Vec::new();
+    let mut samples: Vec<(String, i16)> = Vec::new();
+
+    // Remaining clock per side; `None` of each means untimed (no loss on time is ever
+    // declared). `move_time_limit` is the flat per-move budget for `TimeControl::MoveTime`.
+    let (mut white_clock, mut black_clock, increment, move_time_limit) = match time_control {
+        Some(TimeControl::Clock { initial, increment }) => {
+            (Some(initial), Some(initial), increment, None)
+        }
+        Some(TimeControl::MoveTime(move_time)) => (None, None, Duration::zero(), Some(move_time)),
+        None => (None, None, Duration::zero(), None),
+    };
 
-    // Default time control (infinite)
-    let time_info = TimeInfo::default();
+    // Consecutive-ply streaks for eval-threshold adjudication, tracked in White's
+    // perspective so a single comparison covers both sides.
+    let mut win_leader: Option<chess::Color> = None;
+    let mut win_streak: u32 = 0;
+    let mut draw_streak: u32 = 0;
 
     // Helper function to create PGN from current state
     let create_pgn = |result: GameResult, moves: Vec<String>| {
-        Pgn::new(fen.to_string(), moves, result)
+        let mut pgn = Pgn::new(fen.to_string(), moves, result);
+        if let Some(time_control) = time_control {
+            pgn.add_tag("TimeControl".to_string(), time_control_tag(time_control));
+        }
+        pgn
+    };
+
+    // Bundles a result into the full return tuple, stamping every collected sample with
+    // the game's final outcome.
+    let finish = |result: GameResult,
+                  state: GameState,
+                  moves: Vec<String>,
+                  move_times: Vec<Duration>,
+                  samples: Vec<(String, i16)>| {
+        let pgn = create_pgn(result, moves);
+        let samples = samples.into_iter().map(|(fen, eval)| (fen, eval, result)).collect();
+        (result, state, pgn, move_times, samples)
     };
 
     loop {
@@ -175,40 +266,79 @@ pub fn play_match<E: EvaluateEngine>(
                 } else {
                     GameResult::WhiteWins
                 };
-                return Ok((result, state, create_pgn(result, move_list)));
+                return Ok(finish(result, state, move_list, move_times, samples));
             }
             BoardStatus::Stalemate => {
-                return Ok((GameResult::Draw, state, create_pgn(GameResult::Draw, move_list)));
+                return Ok(finish(GameResult::Draw, state, move_list, move_times, samples));
             }
             BoardStatus::Ongoing => {}
         }
 
         // Check for move limit
         if move_count >= max_moves {
-            return Ok((GameResult::Draw, state, create_pgn(GameResult::Draw, move_list)));
+            return Ok(finish(GameResult::Draw, state, move_list, move_times, samples));
         }
 
-        // Select the engine based on side to move
-        let best_move = if board.side_to_move() == chess::Color::White {
+        let side_to_move = board.side_to_move();
+
+        let time_info = TimeInfo {
+            move_time: move_time_limit,
+            white_time: white_clock,
+            black_time: black_clock,
+            white_increment: if increment.is_zero() { None } else { Some(increment) },
+            black_increment: if increment.is_zero() { None } else { Some(increment) },
+            moves_to_go: None,
+            max_depth: None,
+            max_nodes: None,
+            search_moves: None,
+        };
+
+        // Select the engine based on side to move, timing how long it takes to reply.
+        let think_start = Instant::now();
+        let best_move = if side_to_move == chess::Color::White {
             white_engine.next_move(state.clone(), time_info.clone())
         } else {
             black_engine.next_move(state.clone(), time_info.clone())
         };
+        let elapsed = Duration::from_std(think_start.elapsed()).unwrap_or_else(|_| Duration::zero());
 
         // Get the next move from the engine
         let best_move = match best_move {
             Some(mv) => mv,
             None => {
                 // Engine resigned or couldn't find a move
-                let result = if board.side_to_move() == chess::Color::White {
+                let result = if side_to_move == chess::Color::White {
                     GameResult::BlackWins
                 } else {
                     GameResult::WhiteWins
                 };
-                return Ok((result, state, create_pgn(result, move_list)));
+                return Ok(finish(result, state, move_list, move_times, samples));
             }
         };
 
+        let side_clock = if side_to_move == chess::Color::White {
+            &mut white_clock
+        } else {
+            &mut black_clock
+        };
+
+        // Loss on time: either the per-move budget or the side's own clock ran out.
+        let flagged = move_time_limit.is_some_and(|limit| elapsed > limit)
+            || side_clock.is_some_and(|remaining| remaining <= elapsed);
+        if flagged {
+            let result = if side_to_move == chess::Color::White {
+                GameResult::BlackWins
+            } else {
+                GameResult::WhiteWins
+            };
+            return Ok(finish(result, state, move_list, move_times, samples));
+        }
+
+        if let Some(remaining) = *side_clock {
+            *side_clock = Some(remaining - elapsed + increment);
+        }
+        move_times.push(elapsed);
+
         // Convert move to SAN (Standard Algebraic Notation)
         let san_move = move_to_san(&board, best_move);
         move_list.push(san_move);
@@ -216,7 +346,55 @@ pub fn play_match<E: EvaluateEngine>(
         // Make the move and check for threefold repetition
         let repetition_count = state.make_move(best_move);
         if repetition_count >= 3 {
-            return Ok((GameResult::Draw, state, create_pgn(GameResult::Draw, move_list)));
+            return Ok(finish(GameResult::Draw, state, move_list, move_times, samples));
+        }
+
+        // Record the resulting position's eval and (optionally) adjudicate on it.
+        if let Some(evaluator) = &mut evaluator {
+            let eval = evaluator.evaluate(&state).unwrap();
+            samples.push((state.last_board().to_string(), eval));
+
+            let white_eval = if state.last_board().side_to_move() == chess::Color::White {
+                eval
+            } else {
+                -eval
+            };
+
+            if let Some(adjudication) = adjudication {
+                if white_eval >= adjudication.win_threshold {
+                    win_streak = if win_leader == Some(chess::Color::White) { win_streak + 1 } else { 1 };
+                    win_leader = Some(chess::Color::White);
+                } else if white_eval <= -adjudication.win_threshold {
+                    win_streak = if win_leader == Some(chess::Color::Black) { win_streak + 1 } else { 1 };
+                    win_leader = Some(chess::Color::Black);
+                } else {
+                    win_leader = None;
+                    win_streak = 0;
+                }
+
+                if win_streak >= adjudication.win_plies {
+                    if let Some(leader) = win_leader {
+                        let result = if leader == chess::Color::White {
+                            GameResult::WhiteWins
+                        } else {
+                            GameResult::BlackWins
+                        };
+                        return Ok(finish(result, state, move_list, move_times, samples));
+                    }
+                }
+
+                if state.ply() >= adjudication.draw_min_ply
+                    && white_eval.unsigned_abs() <= adjudication.draw_threshold.unsigned_abs()
+                {
+                    draw_streak += 1;
+                } else {
+                    draw_streak = 0;
+                }
+
+                if draw_streak >= adjudication.draw_plies {
+                    return Ok(finish(GameResult::Draw, state, move_list, move_times, samples));
+                }
+            }
         }
 
         move_count += 1;
@@ -224,6 +402,163 @@ pub fn play_match<E: EvaluateEngine>(
     }
 }
 
+impl FromStr for Pgn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_pgn(s)
+    }
+}
+
+/// Parses a square like "e4" into a `Square`, mirroring `uci::from_uci_move`'s square
+/// parsing.
+fn square_from_str(s: &str) -> Option<Square> {
+    let mut chars = s.chars();
+    let file = chars.next()?;
+    let rank = chars.next()?;
+    if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+        return None;
+    }
+    let file_idx = (file as u8 - b'a') as usize;
+    let rank_idx = (rank as u8 - b'1') as usize;
+    Some(Square::make_square(chess::ALL_RANKS[rank_idx], chess::ALL_FILES[file_idx]))
+}
+
+/// Parses a PGN game from text: the `[Tag "value"]` header block, an optional
+/// `[FEN ...]`/`[SetUp ...]` starting position, the numbered move text, and the
+/// trailing result token. `Pgn::moves` comes back as raw SAN strings -- resolve each one
+/// against a live `Board`/`GameState` with `san_to_move` to actually replay the game.
+pub fn parse_pgn(input: &str) -> Result<Pgn, String> {
+    let starting_fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    let mut tags: Vec<(String, String)> = Vec::new();
+    let mut fen = starting_fen.to_string();
+    let mut movetext = String::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if let Some(inner) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let (key, rest) = inner
+                .split_once(' ')
+                .ok_or_else(|| format!("Malformed PGN tag: {}", line))?;
+            let value = rest.trim().trim_matches('"').to_string();
+            if key == "FEN" {
+                fen = value.clone();
+            }
+            tags.push((key.to_string(), value));
+        } else if !line.is_empty() {
+            movetext.push_str(line);
+            movetext.push(' ');
+        }
+    }
+
+    let mut moves = Vec::new();
+    let mut result = None;
+
+    for token in movetext.split_whitespace() {
+        match token {
+            "1-0" => result = Some(GameResult::WhiteWins),
+            "0-1" => result = Some(GameResult::BlackWins),
+            "1/2-1/2" | "*" => result = Some(GameResult::Draw),
+            _ => {
+                // Strip move-number prefixes like "1." or "12...".
+                let token = token.trim_end_matches('.');
+                if token.is_empty() || token.chars().all(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                moves.push(token.to_string());
+            }
+        }
+    }
+
+    let result = result.unwrap_or_else(|| {
+        match tags.iter().find(|(key, _)| key == "Result").map(|(_, v)| v.as_str()) {
+            Some("1-0") => GameResult::WhiteWins,
+            Some("0-1") => GameResult::BlackWins,
+            _ => GameResult::Draw,
+        }
+    });
+
+    let mut pgn = Pgn::new(fen, moves, result);
+    pgn.tags = tags;
+    Ok(pgn)
+}
+
+/// Resolves a SAN token (e.g. `Nbd7`, `exd5`, `O-O`, `e8=Q`) against the legal moves of
+/// `board`. The inverse of `move_to_san`. Returns `None` if `san` doesn't match exactly
+/// one legal move.
+pub fn san_to_move(board: &Board, san: &str) -> Option<ChessMove> {
+    use chess::{MoveGen, Piece};
+
+    let san = san.trim_end_matches(['+', '#', '!', '?']);
+
+    if san == "O-O" || san == "0-0" || san == "O-O-O" || san == "0-0-0" {
+        let rank = if board.side_to_move() == chess::Color::White { '1' } else { '8' };
+        let source = square_from_str(&format!("e{}", rank))?;
+        let dest_file = if san.len() == 3 { 'g' } else { 'c' };
+        let dest = square_from_str(&format!("{}{}", dest_file, rank))?;
+        return MoveGen::new_legal(board).find(|m| m.get_source() == source && m.get_dest() == dest);
+    }
+
+    // Promotion suffix, e.g. "=Q".
+    let (san, promotion) = if let Some(idx) = san.find('=') {
+        let piece = match san[idx + 1..].chars().next()? {
+            'Q' => Piece::Queen,
+            'R' => Piece::Rook,
+            'B' => Piece::Bishop,
+            'N' => Piece::Knight,
+            _ => return None,
+        };
+        (&san[..idx], Some(piece))
+    } else {
+        (san, None)
+    };
+
+    let (piece, rest) = match san.chars().next()? {
+        'K' => (Piece::King, &san[1..]),
+        'Q' => (Piece::Queen, &san[1..]),
+        'R' => (Piece::Rook, &san[1..]),
+        'B' => (Piece::Bishop, &san[1..]),
+        'N' => (Piece::Knight, &san[1..]),
+        _ => (Piece::Pawn, san),
+    };
+
+    // Drop the capture marker; what remains is optional disambiguation + destination.
+    let rest = rest.replace('x', "");
+    if rest.len() < 2 {
+        return None;
+    }
+    let dest = square_from_str(&rest[rest.len() - 2..])?;
+    let disambiguation = &rest[..rest.len() - 2];
+
+    let candidates: Vec<ChessMove> = MoveGen::new_legal(board)
+        .filter(|m| {
+            board.piece_on(m.get_source()) == Some(piece)
+                && m.get_dest() == dest
+                && m.get_promotion() == promotion
+        })
+        .collect();
+
+    if disambiguation.is_empty() {
+        return (candidates.len() == 1).then(|| candidates[0]);
+    }
+
+    let file_char = disambiguation.chars().find(|c| ('a'..='h').contains(c));
+    let rank_char = disambiguation.chars().find(|c| c.is_ascii_digit());
+
+    let mut matches = candidates.into_iter().filter(|m| {
+        let source = m.get_source();
+        file_char.map_or(true, |f| source.get_file().to_index() as u8 == f as u8 - b'a')
+            && rank_char.map_or(true, |r| source.get_rank().to_index() as u8 == r as u8 - b'1')
+    });
+
+    let found = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+    Some(found)
+}
+
 /// Converts a ChessMove to Standard Algebraic Notation (SAN)
 fn move_to_san(board: &Board, mv: ChessMove) -> String {
     use chess::{Piece, MoveGen, Square};
@@ -339,6 +674,20 @@ mod tests {
     use super::*;
     use crate::engine::AnyMove;
     use crate::evaluation::CountMaterial;
+    use chess::MoveGen;
+
+    /// Sleeps past any reasonable per-move budget before replying, to exercise
+    /// `play_match`'s loss-on-time handling.
+    struct SlowMove;
+
+    impl<T: EvaluateEngine> SearchEngine<T> for SlowMove {
+        fn next_move(&mut self, state: GameState, _time_info: TimeInfo) -> Option<ChessMove> {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            MoveGen::new_legal(&state.last_board()).next()
+        }
+
+        fn clear_search_state(&mut self) {}
+    }
 
     #[test]
     fn test_play_from_startpos() {
@@ -350,16 +699,102 @@ mod tests {
             &mut black,
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
             Some(10),
+            None,
+            None,
+            None,
         );
 
         assert!(result.is_ok());
-        let (game_result, _state, pgn) = result.unwrap();
+        let (game_result, _state, pgn, move_times, samples) = result.unwrap();
         // With AnyMove engines, it should hit the move limit
         assert_eq!(game_result, GameResult::Draw);
         assert_eq!(pgn.moves.len(), 10);
+        assert_eq!(move_times.len(), 10);
+        assert!(samples.is_empty());
         println!("PGN:\n{}", pgn);
     }
 
+    #[test]
+    fn test_play_with_eval_tracking_and_win_adjudication() {
+        let mut white = AnyMove;
+        let mut black = AnyMove;
+        let mut evaluator = CountMaterial;
+
+        // AnyMove plays the first legal move every time regardless of material, so the
+        // eval streak logic itself (not move quality) is what's under test: a threshold of
+        // 0 is crossed on ply 1 and held from then on, so adjudication should fire almost
+        // immediately rather than playing out to the 200-ply default.
+        let adjudication = Adjudication {
+            win_threshold: 0,
+            win_plies: 3,
+            draw_threshold: 0,
+            draw_plies: 3,
+            draw_min_ply: 0,
+        };
+
+        let result = play_match::<CountMaterial>(
+            &mut white,
+            &mut black,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Some(200),
+            None,
+            Some(&mut evaluator),
+            Some(adjudication),
+        );
+
+        assert!(result.is_ok());
+        let (game_result, _state, pgn, _move_times, samples) = result.unwrap();
+        assert_ne!(game_result, GameResult::Draw);
+        assert!(pgn.moves.len() < 200);
+        assert!(!samples.is_empty());
+        for (_fen, _eval, sample_result) in &samples {
+            assert_eq!(*sample_result, game_result);
+        }
+    }
+
+    #[test]
+    fn test_play_with_move_time_control_tags_pgn() {
+        let mut white = AnyMove;
+        let mut black = AnyMove;
+
+        let result = play_match::<CountMaterial>(
+            &mut white,
+            &mut black,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Some(5),
+            Some(TimeControl::MoveTime(Duration::seconds(1))),
+            None,
+            None,
+        );
+
+        assert!(result.is_ok());
+        let (game_result, _state, pgn, move_times, _samples) = result.unwrap();
+        assert_eq!(game_result, GameResult::Draw);
+        assert_eq!(move_times.len(), 5);
+        assert!(pgn.tags.iter().any(|(k, v)| k == "TimeControl" && v == "1/move"));
+    }
+
+    #[test]
+    fn test_play_loses_on_time() {
+        let mut white = SlowMove;
+        let mut black = AnyMove;
+
+        let result = play_match::<CountMaterial>(
+            &mut white,
+            &mut black,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Some(50),
+            Some(TimeControl::MoveTime(Duration::milliseconds(10))),
+            None,
+            None,
+        );
+
+        assert!(result.is_ok());
+        let (game_result, _state, _pgn, _move_times, _samples) = result.unwrap();
+        // White (SlowMove) always overruns the 10ms-per-move budget on its first move.
+        assert_eq!(game_result, GameResult::BlackWins);
+    }
+
     #[test]
     fn test_play_from_checkmate_position() {
         let mut white = AnyMove;
@@ -371,10 +806,13 @@ mod tests {
             &mut black,
             "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
             Some(100),
+            None,
+            None,
+            None,
         );
 
         assert!(result.is_ok());
-        let (game_result, _state, pgn) = result.unwrap();
+        let (game_result, _state, pgn, _move_times, _samples) = result.unwrap();
         assert_eq!(game_result, GameResult::BlackWins);
         println!("PGN:\n{}", pgn);
     }
@@ -389,6 +827,9 @@ mod tests {
             &mut black,
             "invalid fen string",
             Some(100),
+            None,
+            None,
+            None,
         );
 
         assert!(result.is_err());
@@ -410,4 +851,69 @@ mod tests {
         assert!(pgn_str.contains("1. e4 e5 2. Nf3 Nc6"));
         assert!(pgn_str.contains("1/2-1/2"));
     }
+
+    #[test]
+    fn test_pgn_parse_roundtrip() {
+        let text = "[Event \"Test Game\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Nf3 Nc6 3. Bb5 1-0\n";
+        let pgn = Pgn::from_str(text).unwrap();
+
+        assert_eq!(pgn.fen, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        assert_eq!(pgn.moves, vec!["e4", "e5", "Nf3", "Nc6", "Bb5"]);
+        assert_eq!(pgn.result, GameResult::WhiteWins);
+        assert!(pgn.tags.iter().any(|(k, v)| k == "Event" && v == "Test Game"));
+    }
+
+    #[test]
+    fn test_pgn_parse_with_fen_tag() {
+        let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2";
+        let text = format!(
+            "[FEN \"{}\"]\n[SetUp \"1\"]\n\n1. Nf3 *\n",
+            fen
+        );
+        let pgn = Pgn::from_str(&text).unwrap();
+
+        assert_eq!(pgn.fen, fen);
+        assert_eq!(pgn.moves, vec!["Nf3"]);
+        assert_eq!(pgn.result, GameResult::Draw);
+    }
+
+    #[test]
+    fn test_san_to_move_basic_and_capture() {
+        let board = Board::from_str("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let mv = san_to_move(&board, "e4").unwrap();
+        assert_eq!(format!("{}", mv.get_dest()), "e4");
+
+        let board = board.make_move_new(mv);
+        let mv = san_to_move(&board, "d5").unwrap();
+        let board = board.make_move_new(mv);
+
+        let mv = san_to_move(&board, "exd5").unwrap();
+        assert_eq!(format!("{}", mv.get_source()), "e4");
+        assert_eq!(format!("{}", mv.get_dest()), "d5");
+    }
+
+    #[test]
+    fn test_san_to_move_castling() {
+        let board = Board::from_str("rnbqk2r/pppp1ppp/5n2/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4").unwrap();
+        let mv = san_to_move(&board, "O-O").unwrap();
+        assert_eq!(format!("{}", mv.get_source()), "e1");
+        assert_eq!(format!("{}", mv.get_dest()), "g1");
+    }
+
+    #[test]
+    fn test_san_to_move_disambiguation() {
+        let board = Board::from_str("4k3/8/8/8/R6R/8/8/4K3 w - - 0 1").unwrap();
+        let mv = san_to_move(&board, "Rhe4").unwrap();
+        assert_eq!(format!("{}", mv.get_source()), "h4");
+        assert_eq!(format!("{}", mv.get_dest()), "e4");
+    }
+
+    #[test]
+    fn test_san_roundtrips_through_move_to_san() {
+        let board = Board::default();
+        for san in ["e4", "Nf3", "d4"] {
+            let mv = san_to_move(&board, san).unwrap();
+            assert_eq!(move_to_san(&board, mv), san);
+        }
+    }
 }
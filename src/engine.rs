@@ -1,13 +1,15 @@
-use chess::{Board, BoardStatus, ChessMove, MoveGen};
+use chess::{Board, BoardBuilder, BoardStatus, CastleRights, ChessMove, Color, MoveGen, Piece, Square};
+use ort::Error;
 use vampirc_uci::Duration;
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 // For debugging purpose. Returns the first available legal move.
 pub struct AnyMove;
 
 impl<T: EvaluateEngine> SearchEngine<T> for AnyMove {
-    fn next_move(&mut self, state: GameState, _time_info: &Option<TimeInfo>) -> Option<ChessMove> {
+    fn next_move(&mut self, state: GameState, _time_info: TimeInfo) -> Option<ChessMove> {
         MoveGen::new_legal(&state.last_board()).next()
     }
 
@@ -17,15 +19,19 @@ impl<T: EvaluateEngine> SearchEngine<T> for AnyMove {
 }
 
 pub trait EvaluateEngine {
-    /// Returns a quantized (integer-valued) evaluation of the position, from the side to move perspective
-    fn evaluate(state: &GameState) -> i16;
+    /// Returns a quantized (integer-valued) evaluation of the position, from the side to move
+    /// perspective. Takes `&mut self` since NNUE-backed implementors maintain an incremental
+    /// accumulator that gets updated as part of evaluating a position, and is fallible since
+    /// those implementors run inference through the `ort` ONNX runtime.
+    fn evaluate(&mut self, state: &GameState) -> Result<i16, Error>;
 }
 
 pub trait SearchEngine<T: EvaluateEngine> {
-    /// Finds the next move to be played given a GameState and  optional time-control information.
+    /// Finds the next move to be played given a GameState and time-control/search-control
+    /// information (see `TimeInfo`).
     /// Returns an Option because it can technically fail to find a reasonable move.
     /// Default implementation returns the first available legal move
-    fn next_move(&mut self, state: GameState, time_info: &Option<TimeInfo>) -> Option<ChessMove>;
+    fn next_move(&mut self, state: GameState, time_info: TimeInfo) -> Option<ChessMove>;
 
     /// Clear search state (killer moves, history, etc.) when setting a new position
     fn clear_search_state(&mut self);
@@ -33,13 +39,62 @@ pub trait SearchEngine<T: EvaluateEngine> {
     /// Used to keep searching moves on opponents time.
     /// Default implementation does nothing, and it may be left as is.
     fn ponder(&mut self) {}
+
+    /// Resizes the transposition table (or equivalent) to roughly `megabytes` MB, for the
+    /// UCI `Hash` option. Default implementation does nothing, for engines with no such
+    /// table to resize.
+    fn set_hash_size_mb(&mut self, _megabytes: usize) {}
+
+    /// Sets the number of parallel search threads, for the UCI `Threads` option. Default
+    /// implementation does nothing, for engines that only ever search single-threaded.
+    fn set_threads(&mut self, _threads: usize) {}
+}
+
+/// Lets a `Box<dyn SearchEngine<T>>` stand in anywhere a `SearchEngine<T>` is expected, so
+/// callers that need a heterogeneous collection of engines (different concrete search
+/// algorithms sharing one evaluator) aren't forced to go back to an enum or macro per call
+/// site.
+impl<T: EvaluateEngine> SearchEngine<T> for Box<dyn SearchEngine<T>> {
+    fn next_move(&mut self, state: GameState, time_info: TimeInfo) -> Option<ChessMove> {
+        (**self).next_move(state, time_info)
+    }
+
+    fn clear_search_state(&mut self) {
+        (**self).clear_search_state()
+    }
+
+    fn ponder(&mut self) {
+        (**self).ponder()
+    }
+
+    fn set_hash_size_mb(&mut self, megabytes: usize) {
+        (**self).set_hash_size_mb(megabytes)
+    }
+
+    fn set_threads(&mut self, threads: usize) {
+        (**self).set_threads(threads)
+    }
 }
 
-/// Undo information for a single move
+/// Undo information for a single move: just the irreversible state that `make_move`
+/// overwrote (what was captured and where, the castling rights and en-passant square
+/// it moved on from, the halfmove clock), not a full `chess::Board` copy. `undo_last_move`
+/// rebuilds the prior board from the current one plus this diff via `BoardBuilder`
+/// rather than restoring a stored copy.
 #[derive(Debug, Clone, Copy)]
 struct UndoInfo {
     mv: ChessMove,
-    prev_board: Board,
+    /// The piece that stood on `mv.get_source()` before the move (its pre-promotion
+    /// form, if `mv` was a promotion).
+    moved_piece: Piece,
+    /// The piece captured by this move and the square it disappeared from -- the
+    /// en-passant-captured pawn sits one rank off of `mv.get_dest()`, everywhere else
+    /// it's just `mv.get_dest()` itself.
+    captured: Option<(Piece, Square)>,
+    prev_castle_rights: [CastleRights; 2],
+    prev_en_passant: Option<Square>,
+    prev_zobrist: u64,
+    prev_halfmove_clock: u8,
 }
 
 #[derive(Debug, Clone)]
@@ -51,13 +106,28 @@ pub struct GameState {
     /// A map counting the number of times each position was seen so far.
     /// To be used for implementation of the [threefold repetition rule](https://en.wikipedia.org/wiki/Threefold_repetition)
     seen_positions: HashMap<u64, u8>,
+    /// Our own incrementally-updated Zobrist hash of `board` (see `crate::zobrist`),
+    /// independent of `Board::get_hash`. Keys `seen_positions` and is exposed via
+    /// `zobrist` so search engines can key a transposition table off of it.
+    zobrist: u64,
+    /// Plies since the last pawn move or capture, for the [fifty-move rule](https://en.wikipedia.org/wiki/Fifty-move_rule);
+    /// resets to 0 on those moves and otherwise increments. A draw at 100 (50 full moves).
+    halfmove_clock: u8,
 }
 
 impl GameState {
     pub fn from_board(board: Board) -> Self {
-        let mut s = Self::default();
-        s.board = board;
-        s
+        let zobrist = crate::zobrist::hash(&board);
+        let mut seen_positions = HashMap::with_capacity(128);
+        seen_positions.insert(zobrist, 1);
+
+        Self {
+            board,
+            undo_stack: Vec::with_capacity(128),
+            seen_positions,
+            zobrist,
+            halfmove_clock: 0,
+        }
     }
 
     #[inline(always)]
@@ -65,21 +135,55 @@ impl GameState {
         self.board
     }
 
+    /// The current position's Zobrist hash (see `crate::zobrist`), incrementally
+    /// maintained across `make_move`/`undo_last_move`. Search engines use this to key
+    /// their transposition tables.
+    #[inline(always)]
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
+    }
+
     #[inline]
     pub fn make_move(&mut self, mv: ChessMove) -> u8 {
-        // Store undo info
-        let undo_info = UndoInfo {
-            mv,
-            prev_board: self.board,
+        let prev_board = self.board;
+        let prev_zobrist = self.zobrist;
+        let prev_halfmove_clock = self.halfmove_clock;
+
+        let source = mv.get_source();
+        let dest = mv.get_dest();
+        let moved_piece = prev_board.piece_on(source).expect("move source must hold a piece");
+
+        // Same capture-detection as `crate::zobrist::update`: a regular capture sits on
+        // `dest`, while an en-passant capture sits one rank off of it, on the source's rank.
+        let captured = if let Some(captured_piece) = prev_board.piece_on(dest) {
+            Some((captured_piece, dest))
+        } else if moved_piece == Piece::Pawn && source.get_file() != dest.get_file() {
+            Some((Piece::Pawn, Square::make_square(source.get_rank(), dest.get_file())))
+        } else {
+            None
         };
-        self.undo_stack.push(undo_info);
+
+        // A pawn move or a capture irreversibly resets the fifty-move clock.
+        self.halfmove_clock =
+            if moved_piece == Piece::Pawn || captured.is_some() { 0 } else { self.halfmove_clock.saturating_add(1) };
 
         // Make the move
         self.board = self.board.make_move_new(mv);
+        self.zobrist = crate::zobrist::update(self.zobrist, &prev_board, mv, &self.board);
+
+        // Store undo info: just the bits of `prev_board` the move overwrote, not a copy of it.
+        self.undo_stack.push(UndoInfo {
+            mv,
+            moved_piece,
+            captured,
+            prev_castle_rights: [prev_board.castle_rights(Color::White), prev_board.castle_rights(Color::Black)],
+            prev_en_passant: prev_board.en_passant(),
+            prev_zobrist,
+            prev_halfmove_clock,
+        });
 
         // Update repetition tracking
-        let hash = self.board.get_hash();
-        let entry = self.seen_positions.entry(hash).or_insert(0);
+        let entry = self.seen_positions.entry(self.zobrist).or_insert(0);
         *entry += 1;
         *entry
     }
@@ -89,19 +193,81 @@ impl GameState {
         let undo_info = self.undo_stack.pop().unwrap();
 
         // Decrement repetition count
-        let hash = self.board.get_hash();
-        if let Some(count) = self.seen_positions.get_mut(&hash) {
+        if let Some(count) = self.seen_positions.get_mut(&self.zobrist) {
             *count -= 1;
         }
 
-        // Restore previous board
-        self.board = undo_info.prev_board;
+        // Side to move flipped when the move was made, so the mover is whoever isn't
+        // on move now, and a captured piece (if any) belongs to whoever is.
+        let mover = !self.board.side_to_move();
+        let opponent = self.board.side_to_move();
+        let source = undo_info.mv.get_source();
+        let dest = undo_info.mv.get_dest();
+
+        let mut builder = BoardBuilder::from(&self.board);
+        builder[dest] = None;
+        builder[source] = Some((undo_info.moved_piece, mover));
+        if let Some((captured_piece, captured_square)) = undo_info.captured {
+            builder[captured_square] = Some((captured_piece, opponent));
+        }
+        if undo_info.moved_piece == Piece::King {
+            if let Some((rook_from, rook_to)) = crate::zobrist::castling_rook_move(source, dest) {
+                builder[rook_to] = None;
+                builder[rook_from] = Some((Piece::Rook, mover));
+            }
+        }
+        builder.side_to_move(mover);
+        builder.castle_rights(Color::White, undo_info.prev_castle_rights[0]);
+        builder.castle_rights(Color::Black, undo_info.prev_castle_rights[1]);
+        builder.en_passant(undo_info.prev_en_passant);
+
+        // Restore previous board, hash, and fifty-move clock
+        self.board = Board::try_from(&builder).expect("undo_last_move must reconstruct a valid prior position");
+        self.zobrist = undo_info.prev_zobrist;
+        self.halfmove_clock = undo_info.prev_halfmove_clock;
+    }
+
+    /// Passes the turn without playing a move, for null-move pruning in search. Returns
+    /// the previous board/hash so the caller can restore them with `undo_null_move`.
+    /// Deliberately bypasses `undo_stack`/`seen_positions`: a null move isn't part of the
+    /// real game, so it must not affect `ply`, `moves_since`, or repetition counting.
+    /// Panics if the side to move is in check, matching `chess::Board::null_move`.
+    #[inline]
+    pub fn make_null_move(&mut self) -> (Board, u64) {
+        let prev = (self.board, self.zobrist);
+        self.board = self.board.null_move().expect("make_null_move called while in check");
+        self.zobrist = crate::zobrist::hash(&self.board);
+        prev
+    }
+
+    #[inline]
+    pub fn undo_null_move(&mut self, prev: (Board, u64)) {
+        self.board = prev.0;
+        self.zobrist = prev.1;
     }
 
     #[inline]
     pub fn is_draw(&self) -> bool {
-        // Check stalemate
         self.board.status() == BoardStatus::Stalemate
+            || self.halfmove_clock >= 100
+            || self.seen_position_count() >= 3
+    }
+
+    /// Search-tree variant of `is_draw`: treats a single repetition of the current position
+    /// as a draw when it occurs above the root (`ply > 0`), the usual search optimization for
+    /// cutting off lines that would repeat rather than searching them out to the full count.
+    /// At the root (`ply == 0`) the real game history already contributes earlier occurrences,
+    /// so the full threefold count is still required there.
+    #[inline]
+    pub fn is_draw_for_search(&self, ply: usize) -> bool {
+        self.board.status() == BoardStatus::Stalemate
+            || self.halfmove_clock >= 100
+            || self.seen_position_count() >= if ply > 0 { 2 } else { 3 }
+    }
+
+    #[inline(always)]
+    fn seen_position_count(&self) -> u8 {
+        self.seen_positions.get(&self.zobrist).copied().unwrap_or(0)
     }
 
     /// Get the current ply count (for mate distance calculation)
@@ -109,19 +275,19 @@ impl GameState {
     pub fn ply(&self) -> usize {
         self.undo_stack.len()
     }
+
+    /// Returns the moves played after the given ply, in order.
+    /// Used by search engines that retain state across calls (e.g. MCTS tree reuse)
+    /// to figure out which moves happened since they last looked at this game.
+    pub fn moves_since(&self, ply: usize) -> Vec<ChessMove> {
+        let ply = ply.min(self.undo_stack.len());
+        self.undo_stack[ply..].iter().map(|u| u.mv).collect()
+    }
 }
 
 impl Default for GameState {
     fn default() -> Self {
-        let board = Board::default();
-        let mut seen_positions = HashMap::with_capacity(128);
-        seen_positions.insert(board.get_hash(), 1);
-
-        Self {
-            board,
-            undo_stack: Vec::with_capacity(128),
-            seen_positions,
-        }
+        Self::from_board(Board::default())
     }
 }
 
@@ -133,4 +299,10 @@ pub struct TimeInfo {
     pub white_increment: Option<Duration>,
     pub black_increment: Option<Duration>,
     pub moves_to_go: Option<u8>,
+    /// `go depth N`: cap iterative deepening at this depth.
+    pub max_depth: Option<u16>,
+    /// `go nodes N`: abort the search once its node count reaches this.
+    pub max_nodes: Option<u64>,
+    /// `go searchmoves ...`: restrict the root move iterator to just these moves.
+    pub search_moves: Option<Vec<ChessMove>>,
 }
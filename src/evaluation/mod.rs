@@ -1,5 +1,5 @@
 mod nnue;
-pub use nnue::NnueEval;
+pub use nnue::{IncrementalNnueEval, NnueAccumulator, NnueEval};
 use ort::Error;
 pub use pst::PstEval;
 mod pst;
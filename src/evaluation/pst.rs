@@ -1,6 +1,6 @@
 // TODO: Make the code less repetitive.
 
-use chess::{Board, BoardStatus, Color, Piece, Square, BitBoard, File, EMPTY};
+use chess::{ALL_SQUARES, Board, BoardStatus, CastleRights, Color, Piece, Square, BitBoard, File, EMPTY};
 use ort::Error;
 
 use crate::engine::{EvaluateEngine, GameState};
@@ -17,10 +17,76 @@ const QUEEN_VALUE: i16 = 900;
 const BISHOP_PAIR_BONUS: i16 = 50;
 const ROOK_OPEN_FILE_BONUS: i16 = 25;
 const ROOK_SEMI_OPEN_FILE_BONUS: i16 = 15;
-const PASSED_PAWN_BONUS: [i16; 8] = [0, 10, 20, 40, 70, 120, 200, 0]; // By rank
+const PASSED_PAWN_BONUS_MG: [i16; 8] = [0, 10, 20, 40, 70, 120, 200, 0]; // By rank
+const PASSED_PAWN_BONUS_EG: [i16; 8] = [0, 0, 5, 15, 35, 70, 120, 0]; // By rank; grows steeper in the endgame
+const PASSED_PAWN_KING_DISTANCE_BONUS: i16 = 5; // Per square of (enemy king distance - own king distance) to the promotion square, endgame only
+const PASSED_PAWN_FREE_ADVANCE_BONUS: i16 = 10; // Extra credit when the square right in front is empty and unattacked
 const DOUBLED_PAWN_PENALTY: i16 = -15;
 const ISOLATED_PAWN_PENALTY: i16 = -20;
-const KING_SAFETY_PAWN_SHIELD: i16 = 10;
+
+// Rook/queen on the relative 7th rank, trapped rook, and bishop-blocked-by-own-pawns
+const ROOK_ON_7TH_MG: i16 = 47;
+const ROOK_ON_7TH_EG: i16 = 98;
+const QUEEN_ON_7TH_MG: i16 = 27;
+const QUEEN_ON_7TH_EG: i16 = 54;
+const TRAPPED_ROOK_PENALTY: i16 = -90;
+const BISHOP_PAWNS_PENALTY: i16 = -8;
+
+// Tempo: flat tapered credit for the side to move, reflecting the practical advantage
+// of having a move available (initiative, no risk of zugzwang this instant, etc.).
+const TEMPO_BONUS_MG: i16 = 24;
+const TEMPO_BONUS_EG: i16 = 11;
+
+// King danger model (Stockfish-style): per-piece-type weight added to
+// `king_attackers_weight` for each enemy piece that attacks a square in the king ring,
+// plus a per-square weight ("C") for every individual ring square under attack.
+const KING_ATTACK_WEIGHT_KNIGHT: i16 = 2;
+const KING_ATTACK_WEIGHT_BISHOP: i16 = 2;
+const KING_ATTACK_WEIGHT_ROOK: i16 = 3;
+const KING_ATTACK_WEIGHT_QUEEN: i16 = 5;
+const KING_DANGER_ZONE_ATTACK_WEIGHT: i16 = 2;
+
+// Threats: penalty for a minor/rook/queen standing on a square attacked by an enemy
+// pawn, and a flat bonus for any piece that's attacked but left undefended ("hanging").
+// Values loosely follow Stockfish's ThreatenedByPawn/Hanging tables.
+const THREATENED_BY_PAWN_MINOR_MG: i16 = 80;
+const THREATENED_BY_PAWN_MINOR_EG: i16 = 119;
+const THREATENED_BY_PAWN_ROOK_MG: i16 = 117;
+const THREATENED_BY_PAWN_ROOK_EG: i16 = 199;
+const THREATENED_BY_PAWN_QUEEN_MG: i16 = 127;
+const THREATENED_BY_PAWN_QUEEN_EG: i16 = 218;
+const HANGING_BONUS_MG: i16 = 23;
+const HANGING_BONUS_EG: i16 = 20;
+
+// Extra threat terms: a separate penalty for an undefended ("weak") minor piece, and
+// graded bonuses when a lower-valued piece attacks a higher-valued one.
+const WEAK_MINOR_PENALTY_MG: i16 = -25;
+const WEAK_MINOR_PENALTY_EG: i16 = -10;
+const THREAT_MINOR_ON_ROOK_MG: i16 = 35;
+const THREAT_MINOR_ON_ROOK_EG: i16 = 45;
+const THREAT_MINOR_ON_QUEEN_MG: i16 = 50;
+const THREAT_MINOR_ON_QUEEN_EG: i16 = 60;
+const THREAT_ROOK_ON_QUEEN_MG: i16 = 40;
+const THREAT_ROOK_ON_QUEEN_EG: i16 = 50;
+
+// Space: only relevant while there's still a middlegame to maneuver in.
+const SPACE_ACTIVATION_PHASE: i16 = 128;
+const SPACE_BONUS_PER_SQUARE: i16 = 2;
+// Extra bonus for a safe square directly behind a friendly pawn (room to maneuver).
+const SPACE_BEHIND_PAWN_BONUS: i16 = 1;
+
+// Mobility bonus tables, indexed by the number of attacked squares (after masking out
+// friendly pieces and squares covered by enemy pawns), modeled on Stockfish's
+// MobilityBonus[PieceType][attacked]. Sized to the maximum number of squares each piece
+// type can ever attack from an empty board.
+const KNIGHT_MOBILITY_MG: [i16; 9]  = [-30, -20, -10,   0,   5,  10,  15,  20,  25];
+const KNIGHT_MOBILITY_EG: [i16; 9]  = [-30, -15,  -5,   0,   5,  10,  12,  14,  16];
+const BISHOP_MOBILITY_MG: [i16; 14] = [-30, -20, -10,  -5,   0,   5,  10,  15,  20,  22,  24,  26,  28,  30];
+const BISHOP_MOBILITY_EG: [i16; 14] = [-30, -20, -10,  -5,   0,   5,   8,  11,  14,  17,  20,  22,  24,  26];
+const ROOK_MOBILITY_MG: [i16; 15]   = [-20, -15, -10,  -5,   0,   4,   8,  12,  16,  19,  22,  24,  26,  28,  30];
+const ROOK_MOBILITY_EG: [i16; 15]   = [-30, -20, -10,  -2,   6,  14,  22,  28,  34,  38,  42,  45,  48,  50,  52];
+const QUEEN_MOBILITY_MG: [i16; 28]  = [-20, -16, -12,  -8,  -4,   0,   3,   6,   9,  12,  14,  16,  18,  20,  21,  22,  23,  24,  25,  26,  27,  28,  28,  29,  29,  30,  30,  30];
+const QUEEN_MOBILITY_EG: [i16; 28]  = [-20, -15, -10,  -5,   0,   4,   8,  12,  16,  19,  22,  24,  26,  28,  29,  30,  31,  32,  33,  34,  34,  35,  35,  36,  36,  37,  37,  38];
 
 // Endgame evaluation tuning constants
 const KING_PROXIMITY_BONUS_PER_SQUARE: i16 = 10;  // Bonus for attacking King being close to enemy King
@@ -171,12 +237,363 @@ struct EndgameContext {
     black_winning: bool,  // Black has mating material and White has no defense
 }
 
-pub struct PstEval;
+/// Per-term midgame/endgame contribution for each side, as produced by the `*_breakdown`
+/// helpers. `white_mg == white_eg` (and likewise for black) whenever a term doesn't
+/// naturally vary between midgame and endgame (e.g. flat bonuses, or terms that already
+/// apply their own phase scaling internally) -- `total` collapses to the right value
+/// either way, since `interpolate(x, x, phase) == x`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TermScore {
+    pub white_mg: i16,
+    pub white_eg: i16,
+    pub black_mg: i16,
+    pub black_eg: i16,
+}
+
+impl TermScore {
+    fn flat(white: i16, black: i16) -> Self {
+        Self { white_mg: white, white_eg: white, black_mg: black, black_eg: black }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            white_mg: self.white_mg + other.white_mg,
+            white_eg: self.white_eg + other.white_eg,
+            black_mg: self.black_mg + other.black_mg,
+            black_eg: self.black_eg + other.black_eg,
+        }
+    }
+
+    /// Net white-minus-black contribution, interpolated by `phase`.
+    pub fn total(&self, phase: i16) -> i16 {
+        PstEval::interpolate(self.white_mg - self.black_mg, self.white_eg - self.black_eg, phase)
+    }
+}
+
+/// Structured, per-term breakdown of a `PstEval` score, mirroring Stockfish's
+/// `do_trace`/`Tracing::scores`. Built by `PstEval::trace` for debugging and tuning --
+/// the normal `evaluate()` path sums the same terms without keeping the breakdown around.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvalTrace {
+    pub material_pst: TermScore,
+    pub pawns: TermScore,
+    pub bishops: TermScore,
+    pub rooks: TermScore,
+    pub mobility: TermScore,
+    pub threats: TermScore,
+    pub space: TermScore,
+    pub king_safety: TermScore,
+    pub endgame: TermScore,
+    pub phase: i16,
+    /// Final score from White's perspective (unlike `EvaluateEngine::evaluate`, which
+    /// flips sign to the side to move).
+    pub total: i16,
+    /// The `pawn_value` of the `EvalParams` this trace was computed with, used by
+    /// `Display` to print pawn-unit values (Stockfish-style) instead of raw centipawns.
+    pub pawn_value: i16,
+    /// Evaluation margin: the magnitude of the more dangerous side's king-danger term
+    /// (see `PstEval::king_safety_breakdown`), for widening search thresholds in sharp
+    /// positions.
+    pub margin: i16,
+}
+
+impl std::fmt::Display for EvalTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let to_pawns = |cp: i16| cp as f64 / self.pawn_value as f64;
+
+        writeln!(
+            f,
+            "{:<12} | {:>9} | {:>9} | {:>9} | {:>9} | {:>7}",
+            "Term", "White MG", "White EG", "Black MG", "Black EG", "Total"
+        )?;
+        let rows: [(&str, &TermScore); 8] = [
+            ("Material", &self.material_pst),
+            ("Pawns", &self.pawns),
+            ("Bishops", &self.bishops),
+            ("Rooks", &self.rooks),
+            ("Mobility", &self.mobility),
+            ("Threats", &self.threats),
+            ("Space", &self.space),
+            ("King safety", &self.king_safety),
+        ];
+        for (name, term) in rows {
+            writeln!(
+                f,
+                "{:<12} | {:>9.2} | {:>9.2} | {:>9.2} | {:>9.2} | {:>7.2}",
+                name,
+                to_pawns(term.white_mg),
+                to_pawns(term.white_eg),
+                to_pawns(term.black_mg),
+                to_pawns(term.black_eg),
+                to_pawns(term.total(self.phase))
+            )?;
+        }
+        writeln!(
+            f,
+            "{:<12} | {:>9.2} | {:>9.2} | {:>9.2} | {:>9.2} | {:>7.2}",
+            "Endgame",
+            to_pawns(self.endgame.white_mg),
+            to_pawns(self.endgame.white_eg),
+            to_pawns(self.endgame.black_mg),
+            to_pawns(self.endgame.black_eg),
+            to_pawns(self.endgame.total(self.phase))
+        )?;
+        writeln!(f, "Phase: {} / 256", self.phase)?;
+        writeln!(f, "Margin: {:.2}", to_pawns(self.margin))?;
+        write!(f, "Total (White's perspective): {:+.2}", to_pawns(self.total))
+    }
+}
+
+/// Every tunable weight used by `PstEval`'s evaluation terms: material values, PSTs,
+/// pawn/bishop/rook bonuses, king-safety weights, mobility tables, threat/space bonuses
+/// and endgame constants. `Default` matches the values this engine originally shipped
+/// with as plain `const`s; a texel-tuning or SPSA driver can build a mutated copy and
+/// feed it to `PstEval::with_params`, or round-trip one through `PstEval::load`/`save`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EvalParams {
+    pub pawn_value: i16,
+    pub knight_value: i16,
+    pub bishop_value: i16,
+    pub rook_value: i16,
+    pub queen_value: i16,
+
+    pub bishop_pair_bonus: i16,
+    pub rook_open_file_bonus: i16,
+    pub rook_semi_open_file_bonus: i16,
+    pub passed_pawn_bonus_mg: [i16; 8],
+    pub passed_pawn_bonus_eg: [i16; 8],
+    pub passed_pawn_king_distance_bonus: i16,
+    pub passed_pawn_free_advance_bonus: i16,
+    pub doubled_pawn_penalty: i16,
+    pub isolated_pawn_penalty: i16,
+
+    pub rook_on_7th_mg: i16,
+    pub rook_on_7th_eg: i16,
+    pub queen_on_7th_mg: i16,
+    pub queen_on_7th_eg: i16,
+    pub trapped_rook_penalty: i16,
+    pub bishop_pawns_penalty: i16,
+
+    pub tempo_bonus_mg: i16,
+    pub tempo_bonus_eg: i16,
+
+    pub king_attack_weight_knight: i16,
+    pub king_attack_weight_bishop: i16,
+    pub king_attack_weight_rook: i16,
+    pub king_attack_weight_queen: i16,
+    pub king_danger_zone_attack_weight: i16,
+
+    pub threatened_by_pawn_minor_mg: i16,
+    pub threatened_by_pawn_minor_eg: i16,
+    pub threatened_by_pawn_rook_mg: i16,
+    pub threatened_by_pawn_rook_eg: i16,
+    pub threatened_by_pawn_queen_mg: i16,
+    pub threatened_by_pawn_queen_eg: i16,
+    pub hanging_bonus_mg: i16,
+    pub hanging_bonus_eg: i16,
+    pub weak_minor_penalty_mg: i16,
+    pub weak_minor_penalty_eg: i16,
+    pub threat_minor_on_rook_mg: i16,
+    pub threat_minor_on_rook_eg: i16,
+    pub threat_minor_on_queen_mg: i16,
+    pub threat_minor_on_queen_eg: i16,
+    pub threat_rook_on_queen_mg: i16,
+    pub threat_rook_on_queen_eg: i16,
+
+    pub space_activation_phase: i16,
+    pub space_bonus_per_square: i16,
+    pub space_behind_pawn_bonus: i16,
+
+    pub knight_mobility_mg: [i16; 9],
+    pub knight_mobility_eg: [i16; 9],
+    pub bishop_mobility_mg: [i16; 14],
+    pub bishop_mobility_eg: [i16; 14],
+    pub rook_mobility_mg: [i16; 15],
+    pub rook_mobility_eg: [i16; 15],
+    pub queen_mobility_mg: [i16; 28],
+    pub queen_mobility_eg: [i16; 28],
+
+    pub king_proximity_bonus_per_square: i16,
+    pub edge_restriction_bonus_per_square: i16,
+    pub mobility_restriction_bonus_per_square: i16,
+    pub endgame_activation_phase: i16,
+    pub pure_endgame_phase: i16,
+
+    pub pawn_pst_mg: [i16; 64],
+    pub pawn_pst_eg: [i16; 64],
+    pub knight_pst_mg: [i16; 64],
+    pub knight_pst_eg: [i16; 64],
+    pub bishop_pst_mg: [i16; 64],
+    pub bishop_pst_eg: [i16; 64],
+    pub rook_pst_mg: [i16; 64],
+    pub rook_pst_eg: [i16; 64],
+    pub queen_pst_mg: [i16; 64],
+    pub queen_pst_eg: [i16; 64],
+    pub king_pst_mg: [i16; 64],
+    pub king_pst_eg: [i16; 64],
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            pawn_value: PAWN_VALUE,
+            knight_value: KNIGHT_VALUE,
+            bishop_value: BISHOP_VALUE,
+            rook_value: ROOK_VALUE,
+            queen_value: QUEEN_VALUE,
+
+            bishop_pair_bonus: BISHOP_PAIR_BONUS,
+            rook_open_file_bonus: ROOK_OPEN_FILE_BONUS,
+            rook_semi_open_file_bonus: ROOK_SEMI_OPEN_FILE_BONUS,
+            passed_pawn_bonus_mg: PASSED_PAWN_BONUS_MG,
+            passed_pawn_bonus_eg: PASSED_PAWN_BONUS_EG,
+            passed_pawn_king_distance_bonus: PASSED_PAWN_KING_DISTANCE_BONUS,
+            passed_pawn_free_advance_bonus: PASSED_PAWN_FREE_ADVANCE_BONUS,
+            doubled_pawn_penalty: DOUBLED_PAWN_PENALTY,
+            isolated_pawn_penalty: ISOLATED_PAWN_PENALTY,
+
+            rook_on_7th_mg: ROOK_ON_7TH_MG,
+            rook_on_7th_eg: ROOK_ON_7TH_EG,
+            queen_on_7th_mg: QUEEN_ON_7TH_MG,
+            queen_on_7th_eg: QUEEN_ON_7TH_EG,
+            trapped_rook_penalty: TRAPPED_ROOK_PENALTY,
+            bishop_pawns_penalty: BISHOP_PAWNS_PENALTY,
+
+            tempo_bonus_mg: TEMPO_BONUS_MG,
+            tempo_bonus_eg: TEMPO_BONUS_EG,
+
+            king_attack_weight_knight: KING_ATTACK_WEIGHT_KNIGHT,
+            king_attack_weight_bishop: KING_ATTACK_WEIGHT_BISHOP,
+            king_attack_weight_rook: KING_ATTACK_WEIGHT_ROOK,
+            king_attack_weight_queen: KING_ATTACK_WEIGHT_QUEEN,
+            king_danger_zone_attack_weight: KING_DANGER_ZONE_ATTACK_WEIGHT,
+
+            threatened_by_pawn_minor_mg: THREATENED_BY_PAWN_MINOR_MG,
+            threatened_by_pawn_minor_eg: THREATENED_BY_PAWN_MINOR_EG,
+            threatened_by_pawn_rook_mg: THREATENED_BY_PAWN_ROOK_MG,
+            threatened_by_pawn_rook_eg: THREATENED_BY_PAWN_ROOK_EG,
+            threatened_by_pawn_queen_mg: THREATENED_BY_PAWN_QUEEN_MG,
+            threatened_by_pawn_queen_eg: THREATENED_BY_PAWN_QUEEN_EG,
+            hanging_bonus_mg: HANGING_BONUS_MG,
+            hanging_bonus_eg: HANGING_BONUS_EG,
+            weak_minor_penalty_mg: WEAK_MINOR_PENALTY_MG,
+            weak_minor_penalty_eg: WEAK_MINOR_PENALTY_EG,
+            threat_minor_on_rook_mg: THREAT_MINOR_ON_ROOK_MG,
+            threat_minor_on_rook_eg: THREAT_MINOR_ON_ROOK_EG,
+            threat_minor_on_queen_mg: THREAT_MINOR_ON_QUEEN_MG,
+            threat_minor_on_queen_eg: THREAT_MINOR_ON_QUEEN_EG,
+            threat_rook_on_queen_mg: THREAT_ROOK_ON_QUEEN_MG,
+            threat_rook_on_queen_eg: THREAT_ROOK_ON_QUEEN_EG,
+
+            space_activation_phase: SPACE_ACTIVATION_PHASE,
+            space_bonus_per_square: SPACE_BONUS_PER_SQUARE,
+            space_behind_pawn_bonus: SPACE_BEHIND_PAWN_BONUS,
+
+            knight_mobility_mg: KNIGHT_MOBILITY_MG,
+            knight_mobility_eg: KNIGHT_MOBILITY_EG,
+            bishop_mobility_mg: BISHOP_MOBILITY_MG,
+            bishop_mobility_eg: BISHOP_MOBILITY_EG,
+            rook_mobility_mg: ROOK_MOBILITY_MG,
+            rook_mobility_eg: ROOK_MOBILITY_EG,
+            queen_mobility_mg: QUEEN_MOBILITY_MG,
+            queen_mobility_eg: QUEEN_MOBILITY_EG,
+
+            king_proximity_bonus_per_square: KING_PROXIMITY_BONUS_PER_SQUARE,
+            edge_restriction_bonus_per_square: EDGE_RESTRICTION_BONUS_PER_SQUARE,
+            mobility_restriction_bonus_per_square: MOBILITY_RESTRICTION_BONUS_PER_SQUARE,
+            endgame_activation_phase: ENDGAME_ACTIVATION_PHASE,
+            pure_endgame_phase: PURE_ENDGAME_PHASE,
+
+            pawn_pst_mg: PAWN_PST_MG,
+            pawn_pst_eg: PAWN_PST_EG,
+            knight_pst_mg: KNIGHT_PST_MG,
+            knight_pst_eg: KNIGHT_PST_EG,
+            bishop_pst_mg: BISHOP_PST_MG,
+            bishop_pst_eg: BISHOP_PST_EG,
+            rook_pst_mg: ROOK_PST_MG,
+            rook_pst_eg: ROOK_PST_EG,
+            queen_pst_mg: QUEEN_PST_MG,
+            queen_pst_eg: QUEEN_PST_EG,
+            king_pst_mg: KING_PST_MG,
+            king_pst_eg: KING_PST_EG,
+        }
+    }
+}
+
+/// Pawn-structure facts that depend only on pawn placement (not on king position or
+/// other pieces), cached by `PawnHashTable` so they aren't recomputed on every sibling
+/// node in a search: the base pawn-structure score (doubled/isolated penalties plus the
+/// rank-indexed passed-pawn table, before the king-distance and free-advance bonuses
+/// that do depend on the rest of the board), the passed-pawn bitboards, and each side's
+/// open-file mask, which `rooks_breakdown` also needs for open/semi-open-file bonuses.
+#[derive(Clone, Copy, Debug)]
+struct PawnHashEntry {
+    hash: u64,
+    score: TermScore,
+    white_passed: BitBoard,
+    black_passed: BitBoard,
+    white_open_files: BitBoard,
+    black_open_files: BitBoard,
+}
+
+/// Fixed-capacity, always-replace cache from a pawn-only hash to a `PawnHashEntry`, same
+/// spirit as the negamax search's transposition table.
+const PAWN_HASH_SIZE: usize = 1 << 14; // 16,384 slots
+
+#[derive(Clone)]
+struct PawnHashTable {
+    slots: Vec<Option<PawnHashEntry>>,
+}
+
+impl PawnHashTable {
+    fn new(size: usize) -> Self {
+        Self { slots: vec![None; size.next_power_of_two()] }
+    }
+
+    fn get(&self, hash: u64) -> Option<PawnHashEntry> {
+        match self.slots[(hash as usize) & (self.slots.len() - 1)] {
+            Some(entry) if entry.hash == hash => Some(entry),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, entry: PawnHashEntry) {
+        let idx = (entry.hash as usize) & (self.slots.len() - 1);
+        self.slots[idx] = Some(entry);
+    }
+}
+
+#[derive(Clone)]
+pub struct PstEval {
+    params: EvalParams,
+    pawn_table: std::cell::RefCell<PawnHashTable>,
+}
 
 impl PstEval {
     pub fn new() -> Self {
-        Self {}
+        Self { params: EvalParams::default(), pawn_table: std::cell::RefCell::new(PawnHashTable::new(PAWN_HASH_SIZE)) }
     }
+
+    /// Build an evaluator with a custom weight set, e.g. produced by a tuning run.
+    pub fn with_params(params: EvalParams) -> Self {
+        Self { params, pawn_table: std::cell::RefCell::new(PawnHashTable::new(PAWN_HASH_SIZE)) }
+    }
+
+    /// Load a weight set previously written by `save` from a JSON file.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let data = std::fs::read_to_string(path)?;
+        let params: EvalParams = serde_json::from_str(&data)?;
+        Ok(Self::with_params(params))
+    }
+
+    /// Write this evaluator's current weight set to a JSON file, for a tuning driver to
+    /// pick up again via `load`.
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let data = serde_json::to_string_pretty(&self.params)?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
     /// Calculate game phase (0 = opening, 256 = endgame)
     /// Based on remaining material
     #[inline]
@@ -204,9 +621,11 @@ impl PstEval {
         ((mg_score * (256 - phase)) + (eg_score * phase)) / 256
     }
 
-    /// Get piece-square table value for a piece on a square
+    /// Raw (un-interpolated) midgame/endgame piece-square table values for a piece on a
+    /// square. Kept separate from `interpolate` so callers can accumulate true mg/eg
+    /// totals for `EvalTrace` instead of collapsing to a single phase-blended number.
     #[inline]
-    fn pst_value(square: Square, color: Color, mg_table: &[i16; 64], eg_table: &[i16; 64], phase: i16) -> i16 {
+    fn pst_raw(square: Square, color: Color, mg_table: &[i16; 64], eg_table: &[i16; 64]) -> (i16, i16) {
         let idx = if color == Color::White {
             square.to_index()
         } else {
@@ -214,64 +633,50 @@ impl PstEval {
             square.to_index() ^ 56
         };
 
-        Self::interpolate(mg_table[idx], eg_table[idx], phase)
+        (mg_table[idx], eg_table[idx])
     }
 
     /// Evaluate material and position using PSTs
-    fn evaluate_material_pst(board: &Board, phase: i16) -> i16 {
-        let mut score = 0;
+    fn material_pst_breakdown(&self, board: &Board) -> TermScore {
+        let mut term = TermScore::default();
 
         let white = board.color_combined(Color::White);
         let black = board.color_combined(Color::Black);
-
-        let pawns = board.pieces(Piece::Pawn);
-        for square in pawns & white {
-            score += PAWN_VALUE + Self::pst_value(square, Color::White, &PAWN_PST_MG, &PAWN_PST_EG, phase);
-        }
-        for square in pawns & black {
-            score -= PAWN_VALUE + Self::pst_value(square, Color::Black, &PAWN_PST_MG, &PAWN_PST_EG, phase);
-        }
-
-        let knights = board.pieces(Piece::Knight);
-        for square in knights & white {
-            score += KNIGHT_VALUE + Self::pst_value(square, Color::White, &KNIGHT_PST_MG, &KNIGHT_PST_EG, phase);
-        }
-        for square in knights & black {
-            score -= KNIGHT_VALUE + Self::pst_value(square, Color::Black, &KNIGHT_PST_MG, &KNIGHT_PST_EG, phase);
-        }
-
-        let bishops = board.pieces(Piece::Bishop);
-        for square in bishops & white {
-            score += BISHOP_VALUE + Self::pst_value(square, Color::White, &BISHOP_PST_MG, &BISHOP_PST_EG, phase);
-        }
-        for square in bishops & black {
-            score -= BISHOP_VALUE + Self::pst_value(square, Color::Black, &BISHOP_PST_MG, &BISHOP_PST_EG, phase);
-        }
-
-        let rooks = board.pieces(Piece::Rook);
-        for square in rooks & white {
-            score += ROOK_VALUE + Self::pst_value(square, Color::White, &ROOK_PST_MG, &ROOK_PST_EG, phase);
-        }
-        for square in rooks & black {
-            score -= ROOK_VALUE + Self::pst_value(square, Color::Black, &ROOK_PST_MG, &ROOK_PST_EG, phase);
+        let p = &self.params;
+
+        macro_rules! accumulate {
+            ($piece:expr, $value:expr, $mg_table:expr, $eg_table:expr) => {
+                for square in board.pieces($piece) & white {
+                    let (mg, eg) = Self::pst_raw(square, Color::White, &$mg_table, &$eg_table);
+                    term.white_mg += $value + mg;
+                    term.white_eg += $value + eg;
+                }
+                for square in board.pieces($piece) & black {
+                    let (mg, eg) = Self::pst_raw(square, Color::Black, &$mg_table, &$eg_table);
+                    term.black_mg += $value + mg;
+                    term.black_eg += $value + eg;
+                }
+            };
         }
 
-        let queens = board.pieces(Piece::Queen);
-        for square in queens & white {
-            score += QUEEN_VALUE + Self::pst_value(square, Color::White, &QUEEN_PST_MG, &QUEEN_PST_EG, phase);
-        }
-        for square in queens & black {
-            score -= QUEEN_VALUE + Self::pst_value(square, Color::Black, &QUEEN_PST_MG, &QUEEN_PST_EG, phase);
-        }
+        accumulate!(Piece::Pawn, p.pawn_value, p.pawn_pst_mg, p.pawn_pst_eg);
+        accumulate!(Piece::Knight, p.knight_value, p.knight_pst_mg, p.knight_pst_eg);
+        accumulate!(Piece::Bishop, p.bishop_value, p.bishop_pst_mg, p.bishop_pst_eg);
+        accumulate!(Piece::Rook, p.rook_value, p.rook_pst_mg, p.rook_pst_eg);
+        accumulate!(Piece::Queen, p.queen_value, p.queen_pst_mg, p.queen_pst_eg);
 
         // Kings (no material value, just positional)
         let king_sq = (board.pieces(Piece::King) & white).to_square();
-        score += Self::pst_value(king_sq, Color::White, &KING_PST_MG, &KING_PST_EG, phase);
+        let (mg, eg) = Self::pst_raw(king_sq, Color::White, &p.king_pst_mg, &p.king_pst_eg);
+        term.white_mg += mg;
+        term.white_eg += eg;
 
         let king_sq = (board.pieces(Piece::King) & black).to_square();
-        score -= Self::pst_value(king_sq, Color::Black, &KING_PST_MG, &KING_PST_EG, phase);
+        let (mg, eg) = Self::pst_raw(king_sq, Color::Black, &p.king_pst_mg, &p.king_pst_eg);
+        term.black_mg += mg;
+        term.black_eg += eg;
 
-        score
+        term
     }
 
     /// passed pawn: no enemy pawns in front or on adjacent files)
@@ -308,160 +713,661 @@ impl PstEval {
         (enemy_pawns & passed_mask) == EMPTY
     }
 
-    /// Evaluate pawn structure
-    fn evaluate_pawns(board: &Board) -> i16 {
-        let mut score = 0;
+    /// Hash built purely from pawn placement (not an incremental full-position Zobrist
+    /// key, just a fast combination of the two pawn bitboards), used to key
+    /// `PawnHashTable`.
+    #[inline]
+    fn pawn_hash(board: &Board) -> u64 {
+        let white_pawns = (board.pieces(Piece::Pawn) & board.color_combined(Color::White)).0;
+        let black_pawns = (board.pieces(Piece::Pawn) & board.color_combined(Color::Black)).0;
+        white_pawns.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ black_pawns.wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+    }
+
+    /// Compute the pawn-placement-only facts `PawnHashTable` caches: doubled/isolated
+    /// penalties plus the rank-indexed passed-pawn table (the base score, before the
+    /// king-distance/free-advance bonuses that depend on the rest of the board), the
+    /// passed-pawn bitboards, and each side's open-file mask (reused by
+    /// `rooks_breakdown`).
+    fn compute_pawn_entry(&self, board: &Board, hash: u64) -> PawnHashEntry {
+        let p = &self.params;
+        let mut term = TermScore::default();
 
         let white_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::White);
         let black_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::Black);
 
+        let mut white_open_files = EMPTY;
+        let mut black_open_files = EMPTY;
+        for file_idx in 0..8 {
+            let file_mask = chess::get_file(File::from_index(file_idx));
+            if (white_pawns & file_mask) == EMPTY {
+                white_open_files = white_open_files | file_mask;
+            }
+            if (black_pawns & file_mask) == EMPTY {
+                black_open_files = black_open_files | file_mask;
+            }
+        }
+
+        let mut white_passed = EMPTY;
         for square in white_pawns {
             let file = square.get_file().to_index();
             let rank = square.get_rank().to_index();
 
-            // Passed pawn bonus
             if Self::is_passed_pawn(square, Color::White, board) {
-                score += PASSED_PAWN_BONUS[rank];
+                white_passed = white_passed | BitBoard::from_square(square);
+                term.white_mg += p.passed_pawn_bonus_mg[rank];
+                term.white_eg += p.passed_pawn_bonus_eg[rank];
             }
 
-            // Doubled pawns
-            let file_mask = chess::get_file(square.get_file());
-            if (white_pawns & file_mask).popcnt() > 1 {
-                score += DOUBLED_PAWN_PENALTY;
+            if (white_pawns & chess::get_file(square.get_file())).popcnt() > 1 {
+                term.white_mg += p.doubled_pawn_penalty;
+                term.white_eg += p.doubled_pawn_penalty;
             }
 
-            // Isolated pawns (no friendly pawns on adjacent files)
             let adjacent_files = if file == 0 {
                 chess::get_file(File::B)
             } else if file == 7 {
                 chess::get_file(File::G)
             } else {
-                chess::get_file(File::from_index((file - 1) as usize)) | chess::get_file(File::from_index((file + 1) as usize))
+                chess::get_file(File::from_index(file - 1)) | chess::get_file(File::from_index(file + 1))
             };
-
             if (white_pawns & adjacent_files) == EMPTY {
-                score += ISOLATED_PAWN_PENALTY;
+                term.white_mg += p.isolated_pawn_penalty;
+                term.white_eg += p.isolated_pawn_penalty;
             }
         }
 
+        let mut black_passed = EMPTY;
         for square in black_pawns {
             let file = square.get_file().to_index();
             let rank = square.get_rank().to_index();
 
-            // Passed pawn bonus (flipped rank for black)
             if Self::is_passed_pawn(square, Color::Black, board) {
-                score -= PASSED_PAWN_BONUS[7 - rank];
+                black_passed = black_passed | BitBoard::from_square(square);
+                term.black_mg += p.passed_pawn_bonus_mg[7 - rank];
+                term.black_eg += p.passed_pawn_bonus_eg[7 - rank];
             }
 
-            // Doubled pawns
-            let file_mask = chess::get_file(square.get_file());
-            if (black_pawns & file_mask).popcnt() > 1 {
-                score -= DOUBLED_PAWN_PENALTY;
+            if (black_pawns & chess::get_file(square.get_file())).popcnt() > 1 {
+                term.black_mg += p.doubled_pawn_penalty;
+                term.black_eg += p.doubled_pawn_penalty;
             }
 
-            // Isolated pawns
             let adjacent_files = if file == 0 {
                 chess::get_file(File::B)
             } else if file == 7 {
                 chess::get_file(File::G)
             } else {
-                chess::get_file(File::from_index((file - 1) as usize)) | chess::get_file(File::from_index((file + 1) as usize))
+                chess::get_file(File::from_index(file - 1)) | chess::get_file(File::from_index(file + 1))
             };
-
             if (black_pawns & adjacent_files) == EMPTY {
-                score -= ISOLATED_PAWN_PENALTY;
+                term.black_mg += p.isolated_pawn_penalty;
+                term.black_eg += p.isolated_pawn_penalty;
             }
         }
 
-        score
+        PawnHashEntry {
+            hash,
+            score: term,
+            white_passed,
+            black_passed,
+            white_open_files,
+            black_open_files,
+        }
     }
 
-    fn evaluate_bishops(board: &Board) -> i16 {
-        let mut score = 0;
+    /// Look up (or compute and cache) the pawn-placement-only facts for `board`.
+    fn pawn_entry(&self, board: &Board) -> PawnHashEntry {
+        let hash = Self::pawn_hash(board);
+        if let Some(entry) = self.pawn_table.borrow().get(hash) {
+            return entry;
+        }
+
+        let entry = self.compute_pawn_entry(board, hash);
+        self.pawn_table.borrow_mut().insert(entry);
+        entry
+    }
+
+    /// The part of a passed pawn's bonus that depends on the rest of the board (not
+    /// just pawn placement), so it isn't cached in `PawnHashEntry`: an endgame-only
+    /// bonus for the enemy king being far from (and our own king being close to) the
+    /// promotion square, plus a flat bonus when the square directly ahead is empty and
+    /// not attacked by the enemy, i.e. the pawn is free to advance.
+    fn passed_pawn_extra_bonus(&self, board: &Board, square: Square, color: Color) -> TermScore {
+        let p = &self.params;
+        let rank = square.get_rank().to_index();
 
+        let promotion_rank = if color == Color::White { 7 } else { 0 };
+        let promotion_sq = Square::make_square(chess::Rank::from_index(promotion_rank), square.get_file());
+        let own_king = (board.pieces(Piece::King) & board.color_combined(color)).to_square();
+        let enemy_king = (board.pieces(Piece::King) & board.color_combined(!color)).to_square();
+        let own_dist = Self::manhattan_distance(own_king, promotion_sq);
+        let enemy_dist = Self::manhattan_distance(enemy_king, promotion_sq);
+        let mut mg = 0;
+        let mut eg = p.passed_pawn_king_distance_bonus * (enemy_dist - own_dist);
+
+        let advance_rank = if color == Color::White { rank + 1 } else { rank - 1 };
+        let advance_sq = Square::make_square(chess::Rank::from_index(advance_rank), square.get_file());
+        let advance_attacked = (Self::attack_map(board, !color) & BitBoard::from_square(advance_sq)) != EMPTY;
+        if board.piece_on(advance_sq).is_none() && !advance_attacked {
+            mg += p.passed_pawn_free_advance_bonus;
+            eg += p.passed_pawn_free_advance_bonus;
+        }
+
+        if color == Color::White {
+            TermScore { white_mg: mg, white_eg: eg, black_mg: 0, black_eg: 0 }
+        } else {
+            TermScore { white_mg: 0, white_eg: 0, black_mg: mg, black_eg: eg }
+        }
+    }
+
+    /// Evaluate pawn structure: fetch the cached base score (doubled/isolated penalties
+    /// and the rank-indexed passed-pawn table) from the pawn hash table, then layer on
+    /// the board-dependent king-distance/free-advance extras for each passed pawn.
+    fn pawns_breakdown(&self, board: &Board) -> TermScore {
+        let entry = self.pawn_entry(board);
+        let mut term = entry.score;
+
+        for square in entry.white_passed {
+            term = term.add(self.passed_pawn_extra_bonus(board, square, Color::White));
+        }
+        for square in entry.black_passed {
+            term = term.add(self.passed_pawn_extra_bonus(board, square, Color::Black));
+        }
+
+        term
+    }
+
+    /// All squares the same color (light/dark) as `square`, for counting how many
+    /// friendly pawns block a bishop's own diagonals.
+    #[inline]
+    fn same_color_squares(square: Square) -> BitBoard {
+        let parity = (square.get_file().to_index() + square.get_rank().to_index()) % 2;
+        ALL_SQUARES
+            .iter()
+            .filter(|sq| (sq.get_file().to_index() + sq.get_rank().to_index()) % 2 == parity)
+            .fold(EMPTY, |acc, &sq| acc | BitBoard::from_square(sq))
+    }
+
+    fn bishops_breakdown(&self, board: &Board) -> TermScore {
+        let p = &self.params;
         let white_bishops = board.pieces(Piece::Bishop) & board.color_combined(Color::White);
         let black_bishops = board.pieces(Piece::Bishop) & board.color_combined(Color::Black);
+        let white_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::White);
+        let black_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::Black);
+
+        let mut white_score = if white_bishops.popcnt() >= 2 { p.bishop_pair_bonus } else { 0 };
+        let mut black_score = if black_bishops.popcnt() >= 2 { p.bishop_pair_bonus } else { 0 };
+
+        // Own pawns on the bishop's square color get in the way of its diagonals
+        for square in white_bishops {
+            let same_color_pawns = (white_pawns & Self::same_color_squares(square)).popcnt() as i16;
+            white_score += p.bishop_pawns_penalty * same_color_pawns;
+        }
+        for square in black_bishops {
+            let same_color_pawns = (black_pawns & Self::same_color_squares(square)).popcnt() as i16;
+            black_score += p.bishop_pawns_penalty * same_color_pawns;
+        }
+
+        TermScore::flat(white_score, black_score)
+    }
+
+    /// Penalty for a rook buried in the corner behind its own uncastled king: little
+    /// horizontal mobility, stuck on the back rank, with no castling rights left.
+    fn trapped_rook_penalty(&self, board: &Board, color: Color, rook_sq: Square, occupied: BitBoard) -> i16 {
+        let back_rank = if color == Color::White { 0 } else { 7 };
+        if rook_sq.get_rank().to_index() != back_rank {
+            return 0;
+        }
+        if board.castle_rights(color) != CastleRights::NoRights {
+            return 0;
+        }
 
-        // Bishop pair bonus
-        if white_bishops.popcnt() >= 2 {
-            score += BISHOP_PAIR_BONUS;
+        let king_sq = (board.pieces(Piece::King) & board.color_combined(color)).to_square();
+        if king_sq.get_rank().to_index() != back_rank {
+            return 0;
         }
-        if black_bishops.popcnt() >= 2 {
-            score -= BISHOP_PAIR_BONUS;
+
+        let rank_mask = chess::get_rank(rook_sq.get_rank());
+        let horizontal_mobility = (chess::get_rook_moves(rook_sq, occupied) & rank_mask).popcnt();
+        if horizontal_mobility > 3 {
+            return 0;
         }
 
-        score
+        let king_file = king_sq.get_file().to_index();
+        let rook_file = rook_sq.get_file().to_index();
+        if (king_file < 4) == (rook_file < king_file) {
+            self.params.trapped_rook_penalty
+        } else {
+            0
+        }
     }
 
-    fn evaluate_rooks(board: &Board) -> i16 {
-        let mut score = 0;
+    fn rooks_breakdown(&self, board: &Board) -> TermScore {
+        let mut term = TermScore::default();
+        let p = &self.params;
+        let pawn_entry = self.pawn_entry(board);
 
         let white_rooks = board.pieces(Piece::Rook) & board.color_combined(Color::White);
         let black_rooks = board.pieces(Piece::Rook) & board.color_combined(Color::Black);
+        let white_queens = board.pieces(Piece::Queen) & board.color_combined(Color::White);
+        let black_queens = board.pieces(Piece::Queen) & board.color_combined(Color::Black);
         let white_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::White);
         let black_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::Black);
+        let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
+        let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
+        let occupied = *board.combined();
 
-        // White rooks on open/semi-open files
+        let white_7th = chess::get_rank(chess::Rank::from_index(6));
+        let black_7th = chess::get_rank(chess::Rank::from_index(1));
+        let white_on_7th = black_king.get_rank().to_index() == 7 || (black_pawns & white_7th) != EMPTY;
+        let black_on_7th = white_king.get_rank().to_index() == 0 || (white_pawns & black_7th) != EMPTY;
+
+        // White rooks: open/semi-open files, 7th rank, trapped-in-corner penalty
         for square in white_rooks {
             let file_mask = chess::get_file(square.get_file());
-            let has_white_pawns = (white_pawns & file_mask) != EMPTY;
-            let has_black_pawns = (black_pawns & file_mask) != EMPTY;
+            let no_white_pawns = (pawn_entry.white_open_files & file_mask) != EMPTY;
+            let no_black_pawns = (pawn_entry.black_open_files & file_mask) != EMPTY;
+            let is_open = no_white_pawns && no_black_pawns;
+            let is_semi_open = no_white_pawns && !no_black_pawns;
+
+            if is_open {
+                term.white_mg += p.rook_open_file_bonus;
+                term.white_eg += p.rook_open_file_bonus;
+            } else if is_semi_open {
+                term.white_mg += p.rook_semi_open_file_bonus;
+                term.white_eg += p.rook_semi_open_file_bonus;
+            }
 
-            if !has_white_pawns && !has_black_pawns {
-                score += ROOK_OPEN_FILE_BONUS;
-            } else if !has_white_pawns {
-                score += ROOK_SEMI_OPEN_FILE_BONUS;
+            if square.get_rank().to_index() == 6 && white_on_7th {
+                term.white_mg += p.rook_on_7th_mg;
+                term.white_eg += p.rook_on_7th_eg;
             }
+
+            let penalty = self.trapped_rook_penalty(board, Color::White, square, occupied);
+            term.white_mg += penalty;
         }
 
-        // Black rooks on open/semi-open files
+        // Black rooks: open/semi-open files, 7th rank, trapped-in-corner penalty
         for square in black_rooks {
             let file_mask = chess::get_file(square.get_file());
-            let has_white_pawns = (white_pawns & file_mask) != EMPTY;
-            let has_black_pawns = (black_pawns & file_mask) != EMPTY;
+            let no_black_pawns = (pawn_entry.black_open_files & file_mask) != EMPTY;
+            let no_white_pawns = (pawn_entry.white_open_files & file_mask) != EMPTY;
+            let is_open = no_black_pawns && no_white_pawns;
+            let is_semi_open = no_black_pawns && !no_white_pawns;
+
+            if is_open {
+                term.black_mg += p.rook_open_file_bonus;
+                term.black_eg += p.rook_open_file_bonus;
+            } else if is_semi_open {
+                term.black_mg += p.rook_semi_open_file_bonus;
+                term.black_eg += p.rook_semi_open_file_bonus;
+            }
 
-            if !has_white_pawns && !has_black_pawns {
-                score -= ROOK_OPEN_FILE_BONUS;
-            } else if !has_black_pawns {
-                score -= ROOK_SEMI_OPEN_FILE_BONUS;
+            if square.get_rank().to_index() == 1 && black_on_7th {
+                term.black_mg += p.rook_on_7th_mg;
+                term.black_eg += p.rook_on_7th_eg;
             }
+
+            let penalty = self.trapped_rook_penalty(board, Color::Black, square, occupied);
+            term.black_mg += penalty;
         }
 
-        score
+        // Queens on the 7th rank share the same enemy-king/enemy-pawns condition
+        for square in white_queens {
+            if square.get_rank().to_index() == 6 && white_on_7th {
+                term.white_mg += p.queen_on_7th_mg;
+                term.white_eg += p.queen_on_7th_eg;
+            }
+        }
+        for square in black_queens {
+            if square.get_rank().to_index() == 1 && black_on_7th {
+                term.black_mg += p.queen_on_7th_mg;
+                term.black_eg += p.queen_on_7th_eg;
+            }
+        }
+
+        term
     }
 
-    /// Evaluate king safety in middlegame
-    fn evaluate_king_safety(board: &Board, phase: i16) -> i16 {
-        // Only relevant in middlegame
-        if phase > 180 {
-            return 0;
+    /// All squares attacked by `color`'s pawns, used to exclude pawn-controlled squares
+    /// from the mobility area (same idea as Stockfish's `mobilityArea`).
+    #[inline]
+    fn pawn_attacks(board: &Board, color: Color) -> BitBoard {
+        let pawns = board.pieces(Piece::Pawn) & board.color_combined(color);
+        pawns.fold(EMPTY, |attacks, sq| attacks | chess::get_pawn_attacks(sq, color, !EMPTY))
+    }
+
+    /// Attack bitboard of a single piece, irrespective of what's standing on the
+    /// attacked squares. Shared by mobility, king safety and threats so they don't each
+    /// reimplement the per-piece-type dispatch.
+    #[inline]
+    fn piece_attacks(square: Square, piece: Piece, color: Color, occupied: BitBoard) -> BitBoard {
+        match piece {
+            Piece::Pawn => chess::get_pawn_attacks(square, color, !EMPTY),
+            Piece::Knight => chess::get_knight_moves(square),
+            Piece::Bishop => chess::get_bishop_moves(square, occupied),
+            Piece::Rook => chess::get_rook_moves(square, occupied),
+            Piece::Queen => chess::get_bishop_moves(square, occupied) | chess::get_rook_moves(square, occupied),
+            Piece::King => chess::get_king_moves(square),
         }
+    }
 
-        let mut score = 0;
+    /// Union of every square attacked by `color`'s pieces (including the king).
+    fn attack_map(board: &Board, color: Color) -> BitBoard {
+        let occupied = *board.combined();
+        let pieces = board.color_combined(color);
+
+        [Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen, Piece::King]
+            .iter()
+            .flat_map(|&piece| board.pieces(piece) & pieces)
+            .fold(EMPTY, |attacks, square| {
+                attacks | Self::piece_attacks(square, board.piece_on(square).unwrap(), color, occupied)
+            })
+    }
+
+    /// Union of every square attacked by `color`'s pieces of type `piece`. Used to find
+    /// threats made specifically by minors/rooks, rather than the whole side's attack set.
+    #[inline]
+    fn attack_map_for(board: &Board, color: Color, piece: Piece) -> BitBoard {
+        let occupied = *board.combined();
+        (board.pieces(piece) & board.color_combined(color))
+            .fold(EMPTY, |attacks, square| attacks | Self::piece_attacks(square, piece, color, occupied))
+    }
+
+    /// Evaluate threats: penalize minors/rooks/queens sitting on squares attacked by an
+    /// enemy pawn, penalize our own undefended minors, and reward leaving an enemy piece
+    /// attacked but undefended ("hanging") or attacked by a lower-valued piece. A threat
+    /// made *by* White is credited to White's own contribution (and likewise for Black),
+    /// so each side's line shows what it's threatening rather than what it's suffering;
+    /// the weak-minor penalty is the exception and is charged to the minor's owner.
+    fn threats_breakdown(&self, board: &Board) -> TermScore {
+        let mut term = TermScore::default();
+        let p = &self.params;
+
+        let white = board.color_combined(Color::White);
+        let black = board.color_combined(Color::Black);
+
+        let white_pawn_attacks = Self::pawn_attacks(board, Color::White);
+        let black_pawn_attacks = Self::pawn_attacks(board, Color::Black);
+
+        let minors = board.pieces(Piece::Knight) | board.pieces(Piece::Bishop);
+
+        let white_minors_hit = (minors & black & white_pawn_attacks).popcnt() as i16;
+        let black_minors_hit = (minors & white & black_pawn_attacks).popcnt() as i16;
+        term.white_mg += p.threatened_by_pawn_minor_mg * white_minors_hit;
+        term.white_eg += p.threatened_by_pawn_minor_eg * white_minors_hit;
+        term.black_mg += p.threatened_by_pawn_minor_mg * black_minors_hit;
+        term.black_eg += p.threatened_by_pawn_minor_eg * black_minors_hit;
+
+        let white_rooks_hit = (board.pieces(Piece::Rook) & black & white_pawn_attacks).popcnt() as i16;
+        let black_rooks_hit = (board.pieces(Piece::Rook) & white & black_pawn_attacks).popcnt() as i16;
+        term.white_mg += p.threatened_by_pawn_rook_mg * white_rooks_hit;
+        term.white_eg += p.threatened_by_pawn_rook_eg * white_rooks_hit;
+        term.black_mg += p.threatened_by_pawn_rook_mg * black_rooks_hit;
+        term.black_eg += p.threatened_by_pawn_rook_eg * black_rooks_hit;
+
+        let white_queens_hit = (board.pieces(Piece::Queen) & black & white_pawn_attacks).popcnt() as i16;
+        let black_queens_hit = (board.pieces(Piece::Queen) & white & black_pawn_attacks).popcnt() as i16;
+        term.white_mg += p.threatened_by_pawn_queen_mg * white_queens_hit;
+        term.white_eg += p.threatened_by_pawn_queen_eg * white_queens_hit;
+        term.black_mg += p.threatened_by_pawn_queen_mg * black_queens_hit;
+        term.black_eg += p.threatened_by_pawn_queen_eg * black_queens_hit;
+
+        // Hanging: attacked by us, not defended by them (kings are never "hanging").
+        let white_attacks = Self::attack_map(board, Color::White);
+        let black_attacks = Self::attack_map(board, Color::Black);
+        let not_kings = !board.pieces(Piece::King);
+
+        let white_hanging = (black & white_attacks & !black_attacks & not_kings).popcnt() as i16;
+        let black_hanging = (white & black_attacks & !white_attacks & not_kings).popcnt() as i16;
+        term.white_mg += p.hanging_bonus_mg * white_hanging;
+        term.white_eg += p.hanging_bonus_eg * white_hanging;
+        term.black_mg += p.hanging_bonus_mg * black_hanging;
+        term.black_eg += p.hanging_bonus_eg * black_hanging;
+
+        // Weak minors: our own minor attacked by the opponent and left undefended.
+        let white_weak_minors = (minors & white & black_attacks & !white_attacks).popcnt() as i16;
+        let black_weak_minors = (minors & black & white_attacks & !black_attacks).popcnt() as i16;
+        term.white_mg += p.weak_minor_penalty_mg * white_weak_minors;
+        term.white_eg += p.weak_minor_penalty_eg * white_weak_minors;
+        term.black_mg += p.weak_minor_penalty_mg * black_weak_minors;
+        term.black_eg += p.weak_minor_penalty_eg * black_weak_minors;
+
+        // Graded threats: a lower-valued piece attacking a higher-valued one.
+        let white_minor_attacks = Self::attack_map_for(board, Color::White, Piece::Knight) | Self::attack_map_for(board, Color::White, Piece::Bishop);
+        let black_minor_attacks = Self::attack_map_for(board, Color::Black, Piece::Knight) | Self::attack_map_for(board, Color::Black, Piece::Bishop);
+        let white_rook_attacks = Self::attack_map_for(board, Color::White, Piece::Rook);
+        let black_rook_attacks = Self::attack_map_for(board, Color::Black, Piece::Rook);
+
+        let white_minor_on_rook = (board.pieces(Piece::Rook) & black & white_minor_attacks).popcnt() as i16;
+        let black_minor_on_rook = (board.pieces(Piece::Rook) & white & black_minor_attacks).popcnt() as i16;
+        term.white_mg += p.threat_minor_on_rook_mg * white_minor_on_rook;
+        term.white_eg += p.threat_minor_on_rook_eg * white_minor_on_rook;
+        term.black_mg += p.threat_minor_on_rook_mg * black_minor_on_rook;
+        term.black_eg += p.threat_minor_on_rook_eg * black_minor_on_rook;
+
+        let white_minor_on_queen = (board.pieces(Piece::Queen) & black & white_minor_attacks).popcnt() as i16;
+        let black_minor_on_queen = (board.pieces(Piece::Queen) & white & black_minor_attacks).popcnt() as i16;
+        term.white_mg += p.threat_minor_on_queen_mg * white_minor_on_queen;
+        term.white_eg += p.threat_minor_on_queen_eg * white_minor_on_queen;
+        term.black_mg += p.threat_minor_on_queen_mg * black_minor_on_queen;
+        term.black_eg += p.threat_minor_on_queen_eg * black_minor_on_queen;
+
+        let white_rook_on_queen = (board.pieces(Piece::Queen) & black & white_rook_attacks).popcnt() as i16;
+        let black_rook_on_queen = (board.pieces(Piece::Queen) & white & black_rook_attacks).popcnt() as i16;
+        term.white_mg += p.threat_rook_on_queen_mg * white_rook_on_queen;
+        term.white_eg += p.threat_rook_on_queen_eg * white_rook_on_queen;
+        term.black_mg += p.threat_rook_on_queen_mg * black_rook_on_queen;
+        term.black_eg += p.threat_rook_on_queen_eg * black_rook_on_queen;
+
+        term
+    }
+
+    /// The central squares (files C-F) on the three ranks just in front of `color`'s
+    /// home rank, i.e. the area space evaluation cares about.
+    #[inline]
+    fn space_mask(color: Color) -> BitBoard {
+        let files = (2..=5).fold(EMPTY, |acc, f| acc | chess::get_file(File::from_index(f)));
+        let rank_indices: [usize; 3] = if color == Color::White { [1, 2, 3] } else { [4, 5, 6] };
+        let ranks = rank_indices
+            .iter()
+            .fold(EMPTY, |acc, &r| acc | chess::get_rank(chess::Rank::from_index(r)));
+
+        files & ranks
+    }
+
+    /// Weighted count of safe squares within `color`'s space mask: empty of `color`'s
+    /// own pawns, not attacked by an enemy pawn, with an extra weight for squares
+    /// directly behind a friendly pawn. Scaled up the more pieces `color` still has on
+    /// the board, since space matters more in crowded positions.
+    fn space_for_side(&self, board: &Board, color: Color, own_pawns: BitBoard, enemy_pawn_attacks: BitBoard) -> i16 {
+        let safe = Self::space_mask(color) & !own_pawns & !enemy_pawn_attacks;
+
+        // Squares with a friendly pawn one rank further forward, i.e. sheltered behind it.
+        let behind_pawn = match color {
+            Color::White => BitBoard(own_pawns.0 >> 8),
+            Color::Black => BitBoard(own_pawns.0 << 8),
+        };
+
+        let safe_count = safe.popcnt() as i16;
+        let behind_count = (safe & behind_pawn).popcnt() as i16;
+
+        let own_pieces = (board.color_combined(color) & !board.pieces(Piece::Pawn) & !board.pieces(Piece::King)).popcnt() as i16;
+        let weight = own_pieces.max(1);
+
+        (safe_count * self.params.space_bonus_per_square + behind_count * self.params.space_behind_pawn_bonus) * weight
+    }
+
+    /// Evaluate space: reward having more safe maneuvering room in the center during the
+    /// opening/middlegame. Fades out toward `SPACE_ACTIVATION_PHASE` and is zero beyond it.
+    /// The fade is already baked into each side's contribution, so it has no separate
+    /// mg/eg split (`white_mg == white_eg`, same for black).
+    fn space_breakdown(&self, board: &Board, phase: i16) -> TermScore {
+        let activation = self.params.space_activation_phase;
+        if phase >= activation {
+            return TermScore::default();
+        }
 
-        let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
-        let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
         let white_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::White);
         let black_pawns = board.pieces(Piece::Pawn) & board.color_combined(Color::Black);
 
-        // White king pawn shield
-        let white_shield_squares = chess::get_king_moves(white_king);
-        for sq in white_shield_squares {
-            if (white_pawns & BitBoard::from_square(sq)) != EMPTY {
-                score += KING_SAFETY_PAWN_SHIELD;
+        let white_pawn_attacks = Self::pawn_attacks(board, Color::White);
+        let black_pawn_attacks = Self::pawn_attacks(board, Color::Black);
+
+        let fade = activation - phase;
+        let white_space = self.space_for_side(board, Color::White, white_pawns, black_pawn_attacks) * fade / activation;
+        let black_space = self.space_for_side(board, Color::Black, black_pawns, white_pawn_attacks) * fade / activation;
+
+        TermScore::flat(white_space, black_space)
+    }
+
+    /// Evaluate piece mobility: for each knight/bishop/rook/queen, the number of squares
+    /// it attacks (excluding squares held by friendly pieces or covered by enemy pawns),
+    /// looked up in a per-piece-type bonus table.
+    /// Per-piece-type "safe mobility" bonus, mirroring Stockfish's
+    /// `MobilityBonus[PieceType][attacked]`: count the pseudo-legal attacks of each
+    /// knight/bishop/rook/queen, excluding squares held by friendly pieces or attacked
+    /// by an enemy pawn, and index a tapered bonus table by that count.
+    fn mobility_breakdown(&self, board: &Board) -> TermScore {
+        let mut term = TermScore::default();
+        let p = &self.params;
+
+        let white = board.color_combined(Color::White);
+        let black = board.color_combined(Color::Black);
+        let occupied = *board.combined();
+
+        let white_pawn_attacks = Self::pawn_attacks(board, Color::White);
+        let black_pawn_attacks = Self::pawn_attacks(board, Color::Black);
+
+        macro_rules! accumulate {
+            ($piece:expr, $attacks:expr, $own:expr, $enemy_pawn_attacks:expr, $mg_table:expr, $eg_table:expr, $mg_out:expr, $eg_out:expr) => {
+                for square in board.pieces($piece) & $own {
+                    let attacked = ($attacks) & !$own & !$enemy_pawn_attacks;
+                    let count = (attacked.popcnt() as usize).min($mg_table.len() - 1);
+                    $mg_out += $mg_table[count];
+                    $eg_out += $eg_table[count];
+                }
+            };
+        }
+
+        accumulate!(Piece::Knight, chess::get_knight_moves(square), white, black_pawn_attacks, p.knight_mobility_mg, p.knight_mobility_eg, term.white_mg, term.white_eg);
+        accumulate!(Piece::Knight, chess::get_knight_moves(square), black, white_pawn_attacks, p.knight_mobility_mg, p.knight_mobility_eg, term.black_mg, term.black_eg);
+
+        accumulate!(Piece::Bishop, chess::get_bishop_moves(square, occupied), white, black_pawn_attacks, p.bishop_mobility_mg, p.bishop_mobility_eg, term.white_mg, term.white_eg);
+        accumulate!(Piece::Bishop, chess::get_bishop_moves(square, occupied), black, white_pawn_attacks, p.bishop_mobility_mg, p.bishop_mobility_eg, term.black_mg, term.black_eg);
+
+        accumulate!(Piece::Rook, chess::get_rook_moves(square, occupied), white, black_pawn_attacks, p.rook_mobility_mg, p.rook_mobility_eg, term.white_mg, term.white_eg);
+        accumulate!(Piece::Rook, chess::get_rook_moves(square, occupied), black, white_pawn_attacks, p.rook_mobility_mg, p.rook_mobility_eg, term.black_mg, term.black_eg);
+
+        accumulate!(Piece::Queen, chess::get_bishop_moves(square, occupied) | chess::get_rook_moves(square, occupied), white, black_pawn_attacks, p.queen_mobility_mg, p.queen_mobility_eg, term.white_mg, term.white_eg);
+        accumulate!(Piece::Queen, chess::get_bishop_moves(square, occupied) | chess::get_rook_moves(square, occupied), black, white_pawn_attacks, p.queen_mobility_mg, p.queen_mobility_eg, term.black_mg, term.black_eg);
+
+        term
+    }
+
+    /// The squares a king danger model should treat as "near the king": the 8 adjacent
+    /// squares, plus the (up to) three squares two ranks further toward the enemy camp.
+    #[inline]
+    fn king_ring(king_sq: Square, color: Color) -> BitBoard {
+        let mut ring = chess::get_king_moves(king_sq);
+
+        let file = king_sq.get_file().to_index() as i16;
+        let rank = king_sq.get_rank().to_index() as i16;
+        let forward_rank = if color == Color::White { rank + 2 } else { rank - 2 };
+
+        if (0..8).contains(&forward_rank) {
+            for df in -1..=1 {
+                let f = file + df;
+                if (0..8).contains(&f) {
+                    let sq = Square::make_square(
+                        chess::Rank::from_index(forward_rank as usize),
+                        chess::File::from_index(f as usize),
+                    );
+                    ring = ring | BitBoard::from_square(sq);
+                }
             }
         }
 
-        // Black king pawn shield
-        let black_shield_squares = chess::get_king_moves(black_king);
-        for sq in black_shield_squares {
-            if (black_pawns & BitBoard::from_square(sq)) != EMPTY {
-                score -= KING_SAFETY_PAWN_SHIELD;
+        ring
+    }
+
+    /// King danger penalty (centipawns, always >= 0) inflicted on `king_color` by the
+    /// opposing pieces attacking its king ring. Quadratic in the combined attacker
+    /// count/weight/zone-pressure, so a single attacker barely registers but several at
+    /// once become decisive.
+    fn king_danger_penalty(&self, board: &Board, king_color: Color, phase: i16) -> i16 {
+        let p = &self.params;
+        let king_sq = (board.pieces(Piece::King) & board.color_combined(king_color)).to_square();
+        let ring = Self::king_ring(king_sq, king_color);
+
+        let attacker_color = !king_color;
+        let attackers = board.color_combined(attacker_color);
+        let occupied = *board.combined();
+
+        let mut king_attackers_count = 0i16;
+        let mut king_attackers_weight = 0i16;
+        let mut king_adjacent_zone_attacks = 0i16;
+
+        for square in board.pieces(Piece::Knight) & attackers {
+            let attacks = chess::get_knight_moves(square) & ring;
+            if attacks != EMPTY {
+                king_attackers_count += 1;
+                king_attackers_weight += p.king_attack_weight_knight;
+                king_adjacent_zone_attacks += attacks.popcnt() as i16;
+            }
+        }
+        for square in board.pieces(Piece::Bishop) & attackers {
+            let attacks = chess::get_bishop_moves(square, occupied) & ring;
+            if attacks != EMPTY {
+                king_attackers_count += 1;
+                king_attackers_weight += p.king_attack_weight_bishop;
+                king_adjacent_zone_attacks += attacks.popcnt() as i16;
+            }
+        }
+        for square in board.pieces(Piece::Rook) & attackers {
+            let attacks = chess::get_rook_moves(square, occupied) & ring;
+            if attacks != EMPTY {
+                king_attackers_count += 1;
+                king_attackers_weight += p.king_attack_weight_rook;
+                king_adjacent_zone_attacks += attacks.popcnt() as i16;
             }
         }
+        for square in board.pieces(Piece::Queen) & attackers {
+            let attacks = (chess::get_bishop_moves(square, occupied) | chess::get_rook_moves(square, occupied)) & ring;
+            if attacks != EMPTY {
+                king_attackers_count += 1;
+                king_attackers_weight += p.king_attack_weight_queen;
+                king_adjacent_zone_attacks += attacks.popcnt() as i16;
+            }
+        }
+
+        // A lone attacker is usually harmless unless it's backed by a queen on the board.
+        let attacker_has_queen = (board.pieces(Piece::Queen) & attackers) != EMPTY;
+        if king_attackers_count == 0 || (king_attackers_count < 2 && !attacker_has_queen) {
+            return 0;
+        }
+
+        let king_danger = king_attackers_count * king_attackers_weight
+            + p.king_danger_zone_attack_weight * king_adjacent_zone_attacks;
+        let penalty = (king_danger * king_danger) / 4096;
+
+        // Fades out in the endgame, like the rest of king safety.
+        penalty * (256 - phase) / 256
+    }
 
-        // Scale by game phase (less important in endgame)
-        score * (256 - phase) / 256
+    /// Evaluate king safety via the king danger model (see `king_danger_penalty`). The
+    /// endgame fade is already applied inside `king_danger_penalty`, so this is flat
+    /// (`white_mg == white_eg`, same for black).
+    ///
+    /// Also returns an evaluation margin: the magnitude of the larger of the two king
+    /// dangers, i.e. how sharp the position is for whichever side is more exposed. This
+    /// is threaded out to `EvalTrace::margin` rather than discarded so the search layer
+    /// can widen futility/razoring thresholds in sharp positions (Stockfish's approach).
+    fn king_safety_breakdown(&self, board: &Board, phase: i16) -> (TermScore, i16) {
+        let white_danger = self.king_danger_penalty(board, Color::White, phase);
+        let black_danger = self.king_danger_penalty(board, Color::Black, phase);
+        let margin = white_danger.max(black_danger);
+        (TermScore::flat(-white_danger, -black_danger), margin)
     }
 
     /// Check if a color has mating material
@@ -523,108 +1429,149 @@ impl PstEval {
     }
 
     /// Evaluate King proximity in endgames with mating material
-    fn evaluate_king_proximity(board: &Board, phase: i16, context: &EndgameContext) -> i16 {
+    fn king_proximity_breakdown(&self, board: &Board, phase: i16, context: &EndgameContext) -> TermScore {
         // Only relevant in late endgame
-        if phase < ENDGAME_ACTIVATION_PHASE {
-            return 0;
-        }
-
-        let mut score = 0;
-
-        if context.white_winning {
-            // White is trying to mate Black
-            let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
-            let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
-
-            let distance = Self::manhattan_distance(white_king, black_king);
-
-            // Bonus for Kings being close (max 70cp at distance 0)
-            score += (7 - distance.min(7)) * KING_PROXIMITY_BONUS_PER_SQUARE;
+        if phase < self.params.endgame_activation_phase {
+            return TermScore::default();
         }
 
-        if context.black_winning {
-            // Black is trying to mate White
-            let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
-            let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
-
-            let distance = Self::manhattan_distance(white_king, black_king);
+        let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
+        let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
+        let distance = Self::manhattan_distance(white_king, black_king);
+        // Bonus for Kings being close (max 70cp at distance 0)
+        let bonus = (7 - distance.min(7)) * self.params.king_proximity_bonus_per_square;
 
-            // Same logic for black
-            score -= (7 - distance.min(7)) * KING_PROXIMITY_BONUS_PER_SQUARE;
-        }
+        // White is trying to mate Black, or vice versa; same distance, same bonus either way.
+        let white = if context.white_winning { bonus } else { 0 };
+        let black = if context.black_winning { bonus } else { 0 };
 
-        score
+        TermScore::flat(white, black)
     }
 
     /// Evaluate enemy King restriction to edges in mating endgames
-    fn evaluate_king_edge_restriction(board: &Board, phase: i16, context: &EndgameContext) -> i16 {
+    fn king_edge_restriction_breakdown(&self, board: &Board, phase: i16, context: &EndgameContext) -> TermScore {
         // Only relevant in late endgame
-        if phase < ENDGAME_ACTIVATION_PHASE {
-            return 0;
+        if phase < self.params.endgame_activation_phase {
+            return TermScore::default();
         }
 
-        let mut score = 0;
-
-        if context.white_winning {
+        let white = if context.white_winning {
             let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
             let edge_dist = Self::edge_distance(black_king);
-
             // Big bonus for enemy King near edges (90cp when on edge)
-            score += (3 - edge_dist.min(3)) * EDGE_RESTRICTION_BONUS_PER_SQUARE;
-        }
+            (3 - edge_dist.min(3)) * self.params.edge_restriction_bonus_per_square
+        } else {
+            0
+        };
 
-        if context.black_winning {
+        let black = if context.black_winning {
             let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
             let edge_dist = Self::edge_distance(white_king);
+            (3 - edge_dist.min(3)) * self.params.edge_restriction_bonus_per_square
+        } else {
+            0
+        };
 
-            score -= (3 - edge_dist.min(3)) * EDGE_RESTRICTION_BONUS_PER_SQUARE;
-        }
-
-        score
+        TermScore::flat(white, black)
     }
 
     /// Estimate mate distance and give bonus for positions closer to mate
-    fn evaluate_mate_progress(board: &Board, phase: i16, context: &EndgameContext) -> i16 {
+    fn mate_progress_breakdown(&self, board: &Board, phase: i16, context: &EndgameContext) -> TermScore {
         // Only in pure endgames
-        if phase < PURE_ENDGAME_PHASE {
-            return 0;
+        if phase < self.params.pure_endgame_phase {
+            return TermScore::default();
         }
 
-        let mut score = 0;
+        let black = board.color_combined(Color::Black);
+        let white = board.color_combined(Color::White);
 
         // If winning side, add bonus based on restricting King mobility
-        if context.white_winning {
+        let white_score = if context.white_winning {
             // Count mobility of black King (fewer moves = closer to mate)
-            let black_king = (board.pieces(Piece::King) & board.color_combined(Color::Black)).to_square();
+            let black_king = (board.pieces(Piece::King) & black).to_square();
             let king_moves = chess::get_king_moves(black_king);
-
-            // Filter out squares occupied by black pieces or attacked by white
-            let black = board.color_combined(Color::Black);
-            let white = board.color_combined(Color::White);
-
             // Simple mobility check: exclude squares with black pieces
             // (A full attack detection would be more accurate but slower)
-            let legal_king_squares = king_moves & !black & !white;
-            let legal_king_moves = legal_king_squares.popcnt() as i16;
-
+            let legal_king_moves = (king_moves & !black & !white).popcnt() as i16;
             // Bonus for restricting King mobility (5cp per restricted square)
-            score += (8 - legal_king_moves) * MOBILITY_RESTRICTION_BONUS_PER_SQUARE;
-        }
+            (8 - legal_king_moves) * self.params.mobility_restriction_bonus_per_square
+        } else {
+            0
+        };
 
-        if context.black_winning {
-            let white_king = (board.pieces(Piece::King) & board.color_combined(Color::White)).to_square();
+        let black_score = if context.black_winning {
+            let white_king = (board.pieces(Piece::King) & white).to_square();
             let king_moves = chess::get_king_moves(white_king);
+            let legal_king_moves = (king_moves & !black & !white).popcnt() as i16;
+            (8 - legal_king_moves) * self.params.mobility_restriction_bonus_per_square
+        } else {
+            0
+        };
 
-            let black = board.color_combined(Color::Black);
-            let white = board.color_combined(Color::White);
+        TermScore::flat(white_score, black_score)
+    }
+
+    /// Combined late-endgame mating-progress term (king proximity, edge restriction,
+    /// mobility restriction), gated on `EndgameContext` and bucketed together in
+    /// `EvalTrace` since they only ever fire together for the side that's winning.
+    fn endgame_breakdown(&self, board: &Board, phase: i16) -> TermScore {
+        let context = Self::analyze_endgame(board);
 
-            let legal_king_squares = king_moves & !black & !white;
-            let legal_king_moves = legal_king_squares.popcnt() as i16;
+        self.king_proximity_breakdown(board, phase, &context)
+            .add(self.king_edge_restriction_breakdown(board, phase, &context))
+            .add(self.mate_progress_breakdown(board, phase, &context))
+    }
 
-            score -= (8 - legal_king_moves) * MOBILITY_RESTRICTION_BONUS_PER_SQUARE;
+    /// Build a full per-term breakdown of the evaluation of `board`, from White's
+    /// perspective. Useful for debugging and for tuning individual term weights; the
+    /// normal `EvaluateEngine::evaluate` path sums these same terms without keeping the
+    /// breakdown around.
+    pub fn trace(&self, board: &Board) -> EvalTrace {
+        let phase = Self::game_phase(board);
+
+        let material_pst = self.material_pst_breakdown(board);
+        let pawns = self.pawns_breakdown(board);
+        let bishops = self.bishops_breakdown(board);
+        let rooks = self.rooks_breakdown(board);
+        let mobility = self.mobility_breakdown(board);
+        let threats = self.threats_breakdown(board);
+        let space = self.space_breakdown(board, phase);
+        let (king_safety, margin) = self.king_safety_breakdown(board, phase);
+        let endgame = self.endgame_breakdown(board, phase);
+
+        let total = material_pst.total(phase)
+            + pawns.total(phase)
+            + bishops.total(phase)
+            + rooks.total(phase)
+            + mobility.total(phase)
+            + threats.total(phase)
+            + space.total(phase)
+            + king_safety.total(phase)
+            + endgame.total(phase);
+
+        EvalTrace {
+            material_pst,
+            pawns,
+            bishops,
+            rooks,
+            mobility,
+            threats,
+            space,
+            king_safety,
+            endgame,
+            phase,
+            total,
+            pawn_value: self.params.pawn_value,
+            margin,
         }
+    }
 
-        score
+    /// The evaluation margin at `board`: the magnitude of the more dangerous side's
+    /// king-danger term (see `king_safety_breakdown`), in centipawns. A companion to
+    /// `evaluate`/`trace` that the search layer can use to widen futility/razoring
+    /// thresholds in sharp, king-hunt-prone positions.
+    pub fn eval_margin(&self, board: &Board) -> i16 {
+        self.trace(board).margin
     }
 }
 
@@ -641,36 +1588,202 @@ impl EvaluateEngine for PstEval {
             return Ok(-MATE_VALUE + state.ply() as i16);
         }
 
+        // `trace` computes the same per-term breakdown used for debugging/tuning; we
+        // just sum it up here instead of keeping it around.
+        let mut score = self.trace(&board).total;
+
+        // Tempo bonus: credit the side to move with a tapered flat bonus before the
+        // final sign flip below, so it always ends up in favor of whoever is to move.
         let phase = Self::game_phase(&board);
+        let tempo = Self::interpolate(self.params.tempo_bonus_mg, self.params.tempo_bonus_eg, phase);
+        score += if board.side_to_move() == Color::White { tempo } else { -tempo };
 
-        let mut score = 0;
+        // Return from side to move perspective
+        if board.side_to_move() == Color::White {
+            Ok(score)
+        } else {
+            Ok(-score)
+        }
+    }
+}
 
-        // Material + PST evaluation
-        score += Self::evaluate_material_pst(&board, phase);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_mobility_breakdown_counts_attacked_squares() {
+        // A knight on d4 with nothing else on the board attacks all 8 of its squares.
+        let board = Board::from_str("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1").unwrap();
+        let eval = PstEval::new();
+        let term = eval.mobility_breakdown(&board);
+
+        assert_eq!(term.white_mg, KNIGHT_MOBILITY_MG[8]);
+        assert_eq!(term.white_eg, KNIGHT_MOBILITY_EG[8]);
+        assert_eq!(term.black_mg, 0);
+        assert_eq!(term.black_eg, 0);
+    }
 
-        // Pawn structure
-        score += Self::evaluate_pawns(&board);
+    #[test]
+    fn test_king_danger_penalty_requires_two_attackers() {
+        // With the default weights two knights only reach a danger of 14 (14 * 14 / 4096
+        // rounds to 0), so raise the per-square zone weight to make the gate observable.
+        let eval = PstEval::with_params(EvalParams {
+            king_danger_zone_attack_weight: 40,
+            ..EvalParams::default()
+        });
+
+        // A single knight reaching into Black's king ring isn't enough to score a penalty.
+        let one_attacker = Board::from_str("6k1/8/4N3/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(eval.king_danger_penalty(&one_attacker, Color::Black, 0), 0);
+
+        // A second attacker tips it over the `king_attackers_count >= 2` threshold.
+        let two_attackers = Board::from_str("6k1/8/4N3/8/5N2/8/8/4K3 w - - 0 1").unwrap();
+        assert!(eval.king_danger_penalty(&two_attackers, Color::Black, 0) > 0);
+    }
 
-        // Bishop evaluation
-        score += Self::evaluate_bishops(&board);
+    #[test]
+    fn test_threats_breakdown_credits_hanging_piece() {
+        // Black's knight on d5 is attacked by the white queen and defended by nothing.
+        let board = Board::from_str("4k3/8/8/3n4/8/8/8/3QK3 w - - 0 1").unwrap();
+        let eval = PstEval::new();
+        let term = eval.threats_breakdown(&board);
+
+        // White is credited for the hanging piece...
+        assert_eq!(term.white_mg, HANGING_BONUS_MG);
+        assert_eq!(term.white_eg, HANGING_BONUS_EG);
+        // ...and Black separately pays the "weak minor" penalty for leaving it undefended.
+        assert_eq!(term.black_mg, WEAK_MINOR_PENALTY_MG);
+        assert_eq!(term.black_eg, WEAK_MINOR_PENALTY_EG);
+    }
 
-        // Rook evaluation
-        score += Self::evaluate_rooks(&board);
+    #[test]
+    fn test_is_passed_pawn_edge_and_blocked_files() {
+        let passed = Board::from_str("4k3/8/8/8/P7/8/8/4K3 w - - 0 1").unwrap();
+        assert!(PstEval::is_passed_pawn(Square::A4, Color::White, &passed));
 
-        // King safety
-        score += Self::evaluate_king_safety(&board, phase);
+        // A black pawn on the adjacent b-file, ahead of a4, blocks it from being passed.
+        let blocked = Board::from_str("4k3/1p6/8/8/P7/8/8/4K3 w - - 0 1").unwrap();
+        assert!(!PstEval::is_passed_pawn(Square::A4, Color::White, &blocked));
+    }
 
-        // Endgame-specific evaluation (compute context once, use for all three functions)
-        let endgame_context = Self::analyze_endgame(&board);
-        score += Self::evaluate_king_proximity(&board, phase, &endgame_context);
-        score += Self::evaluate_king_edge_restriction(&board, phase, &endgame_context);
-        score += Self::evaluate_mate_progress(&board, phase, &endgame_context);
+    #[test]
+    fn test_rooks_breakdown_open_file_and_7th_rank() {
+        // White rook on the fully open a-file, on the relative 7th rank with the enemy
+        // king still on its home rank.
+        let board = Board::from_str("7k/R7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let eval = PstEval::new();
+        let term = eval.rooks_breakdown(&board);
+
+        assert_eq!(term.white_mg, ROOK_OPEN_FILE_BONUS + ROOK_ON_7TH_MG);
+        assert_eq!(term.white_eg, ROOK_OPEN_FILE_BONUS + ROOK_ON_7TH_EG);
+        assert_eq!(term.black_mg, 0);
+        assert_eq!(term.black_eg, 0);
+    }
 
-        // Return from side to move perspective
-        if board.side_to_move() == Color::White {
-            Ok(score)
-        } else {
-            Ok(-score)
-        }
+    #[test]
+    fn test_trapped_rook_penalty_requires_no_castle_rights() {
+        let eval = PstEval::new();
+
+        let trapped = Board::from_str("4k3/8/8/8/8/8/8/6KR w - - 0 1").unwrap();
+        let occupied = *trapped.combined();
+        assert_eq!(
+            eval.trapped_rook_penalty(&trapped, Color::White, Square::H1, occupied),
+            TRAPPED_ROOK_PENALTY
+        );
+
+        // Same rook/king shape, but White still has castling rights -- not actually stuck.
+        let can_castle = Board::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let occupied = *can_castle.combined();
+        assert_eq!(eval.trapped_rook_penalty(&can_castle, Color::White, Square::H1, occupied), 0);
+    }
+
+    #[test]
+    fn test_bishops_breakdown_pawn_color_penalty() {
+        // The bishop on c1 and the pawn on e3 share the same square color.
+        let board = Board::from_str("4k3/8/8/8/8/4P3/8/2B1K3 w - - 0 1").unwrap();
+        let eval = PstEval::new();
+        let term = eval.bishops_breakdown(&board);
+
+        assert_eq!(term.white_mg, BISHOP_PAWNS_PENALTY);
+        assert_eq!(term.white_eg, BISHOP_PAWNS_PENALTY);
+        assert_eq!(term.black_mg, 0);
+    }
+
+    #[test]
+    fn test_space_breakdown_fades_out_past_activation_phase() {
+        let board = Board::from_str("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let eval = PstEval::new();
+
+        // With no pieces and no pawns, all 12 squares of each side's space mask are safe.
+        let active = eval.space_breakdown(&board, 0);
+        assert_eq!(active.white_mg, 12 * SPACE_BONUS_PER_SQUARE);
+        assert_eq!(active.black_mg, 12 * SPACE_BONUS_PER_SQUARE);
+
+        let inactive = eval.space_breakdown(&board, SPACE_ACTIVATION_PHASE);
+        assert_eq!(inactive.white_mg, 0);
+        assert_eq!(inactive.black_mg, 0);
+    }
+
+    #[test]
+    fn test_eval_params_save_load_roundtrip() {
+        let mut params = EvalParams::default();
+        params.pawn_value = 105;
+        params.bishop_pair_bonus = 42;
+        let eval = PstEval::with_params(params);
+
+        let path = std::env::temp_dir().join("cheese-engine-eval-params-roundtrip-test.json");
+        eval.save(path.to_str().unwrap()).unwrap();
+        let loaded = PstEval::load(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.params.pawn_value, 105);
+        assert_eq!(loaded.params.bishop_pair_bonus, 42);
+    }
+
+    #[test]
+    fn test_pawn_hash_table_caches_and_distinguishes_positions() {
+        let eval = PstEval::new();
+        let board_a = Board::from_str("4k3/8/8/8/8/8/P7/4K3 w - - 0 1").unwrap();
+        let board_b = Board::from_str("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        let entry_a1 = eval.pawn_entry(&board_a);
+        let entry_a2 = eval.pawn_entry(&board_a);
+        assert_eq!(entry_a1.hash, entry_a2.hash);
+        assert_eq!(entry_a1.score.white_mg, entry_a2.score.white_mg);
+
+        let entry_b = eval.pawn_entry(&board_b);
+        assert_ne!(entry_a1.hash, entry_b.hash);
+    }
+
+    #[test]
+    fn test_trace_total_matches_sum_of_terms() {
+        let board = Board::default();
+        let eval = PstEval::new();
+        let trace = eval.trace(&board);
+
+        let expected = trace.material_pst.total(trace.phase)
+            + trace.pawns.total(trace.phase)
+            + trace.bishops.total(trace.phase)
+            + trace.rooks.total(trace.phase)
+            + trace.mobility.total(trace.phase)
+            + trace.threats.total(trace.phase)
+            + trace.space.total(trace.phase)
+            + trace.king_safety.total(trace.phase)
+            + trace.endgame.total(trace.phase);
+
+        assert_eq!(trace.total, expected);
+    }
+
+    #[test]
+    fn test_eval_margin_matches_larger_king_danger() {
+        let eval = PstEval::new();
+        let board = Board::from_str("6k1/8/4N3/8/5N2/8/8/4K3 w - - 0 1").unwrap();
+        let phase = PstEval::game_phase(&board);
+
+        let white_danger = eval.king_danger_penalty(&board, Color::White, phase);
+        let black_danger = eval.king_danger_penalty(&board, Color::Black, phase);
+        assert_eq!(eval.eval_margin(&board), white_danger.max(black_danger));
     }
 }
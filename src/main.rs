@@ -3,13 +3,16 @@ mod engine;
 mod mcts;
 mod negamax;
 mod evaluation;
+mod tournament;
 mod uci;
+mod zobrist;
 
 use fern;
 use log;
 
+use crate::engine::SearchEngine;
 use crate::evaluation::{PstEval};
-use crate::mcts::{MCTS, MCTSEngine};
+use crate::mcts::{MCTSEngine, RandomRollout, UctPolicy};
 use crate::negamax::Negamax;
 use crate::uci::uci_loop;
 use chess::{Board, Square};
@@ -50,9 +53,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 
     log::info!("Starting UCI loop");
-    let eval = PstEval;
-    let mut engine = Negamax::new(eval);
-    uci_loop::<PstEval, _>(&mut engine);
-    
+
+    // `--nnue <path>` swaps the hand-crafted PstEval for a network loaded from an ONNX
+    // file; with no path supplied PstEval remains the default fallback.
+    let args: Vec<String> = std::env::args().collect();
+    let nnue_path = args.windows(2).find(|w| w[0] == "--nnue").map(|w| w[1].clone());
+
+    if let Some(path) = nnue_path {
+        let mut nnue_engine = Negamax::new(NnueEval::load(&path)?);
+        uci_loop::<NnueEval, _>(&mut nnue_engine);
+        return Ok(());
+    }
+
+    // Pick the search algorithm at startup, keeping the rest of the UCI plumbing
+    // identical for either one since both implement `SearchEngine<PstEval>`.
+    let use_mcts = std::env::args().any(|arg| arg == "--mcts");
+
+    let mut negamax_engine = Negamax::new(PstEval::new());
+    let mut mcts_engine = MCTSEngine::new(PstEval::new(), RandomRollout, UctPolicy::default(), 1);
+
+    let engine: &mut dyn SearchEngine<PstEval> = if use_mcts {
+        &mut mcts_engine
+    } else {
+        &mut negamax_engine
+    };
+
+    uci_loop::<PstEval, _>(engine);
+
     Ok(())
 }
@@ -1,61 +1,223 @@
 use std::{
+    collections::HashMap,
     i16,
     time::{Duration, Instant},
 };
 use log;
+use rand::Rng;
 use crate::{engine::SearchEngine};
 
 use chess::{BoardStatus, ChessMove, Game, MoveGen};
+use crossbeam::thread as cb_thread;
 
 use crate::engine::{EvaluateEngine, GameState, TimeInfo};
 
+/// Outcome of a single rollout, from the perspective of the side to move at the node
+/// the rollout started from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayoutOutcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl PlayoutOutcome {
+    fn as_score(self) -> f32 {
+        match self {
+            PlayoutOutcome::Win => 1.0,
+            PlayoutOutcome::Draw => 0.5,
+            PlayoutOutcome::Loss => 0.0,
+        }
+    }
+}
+
+/// Chooses which move to play while running a rollout to a terminal position (or depth
+/// limit). Kept as a trait so `MCTSEngine` can swap in different playout strategies.
+pub trait PlayoutPolicy<E: EvaluateEngine> {
+    fn select_move(&mut self, board: &chess::Board, legal_moves: &[ChessMove], evaluator: &mut E) -> ChessMove;
+}
+
+/// Plays uniformly at random among legal moves. Cheap, unbiased, the classic MCTS default.
+#[derive(Clone)]
+pub struct RandomRollout;
+
+impl<E: EvaluateEngine> PlayoutPolicy<E> for RandomRollout {
+    fn select_move(&mut self, _board: &chess::Board, legal_moves: &[ChessMove], _evaluator: &mut E) -> ChessMove {
+        let idx = rand::thread_rng().gen_range(0..legal_moves.len());
+        legal_moves[idx]
+    }
+}
+
+/// Epsilon-greedy rollout: most of the time grabs the move gaining the most material
+/// (via MVV-LVA-style piece values), otherwise falls back to a uniformly random move.
+/// Keeps rollouts cheap while steering them away from obviously bad captures.
+#[derive(Clone)]
+pub struct GreedyRollout {
+    epsilon: f64,
+}
+
+impl GreedyRollout {
+    pub fn new(epsilon: f64) -> Self {
+        Self { epsilon }
+    }
+
+    fn capture_gain(board: &chess::Board, mv: ChessMove) -> i16 {
+        const PIECE_VALUES: [i16; 6] = [100, 320, 330, 500, 900, 0]; // P, N, B, R, Q, K
+        match board.piece_on(mv.get_dest()) {
+            Some(victim) => PIECE_VALUES[victim.to_index()],
+            None => 0,
+        }
+    }
+}
+
+impl<E: EvaluateEngine> PlayoutPolicy<E> for GreedyRollout {
+    fn select_move(&mut self, board: &chess::Board, legal_moves: &[ChessMove], _evaluator: &mut E) -> ChessMove {
+        if rand::thread_rng().gen_bool(self.epsilon) {
+            let idx = rand::thread_rng().gen_range(0..legal_moves.len());
+            return legal_moves[idx];
+        }
+
+        *legal_moves
+            .iter()
+            .max_by_key(|&&mv| Self::capture_gain(board, mv))
+            .expect("legal_moves is non-empty")
+    }
+}
+
+// Rollouts stop and fall back to a static evaluation after this many plies, so a
+// playout policy that never finds a terminal position can't run away.
+const ROLLOUT_DEPTH_LIMIT: usize = 48;
+// Static-eval margin (centipawns) beyond which a depth-limited rollout is scored as a
+// decisive result rather than a draw.
+const ROLLOUT_DECISIVE_MARGIN: i16 = 50;
+
 #[derive(Default)]
 pub struct MCTSNode {
     state : GameState,
     visits : u16,
-    score : i16,
-    children : Vec<usize>,
-    is_explored : bool
+    // Sum of per-rollout outcomes (1.0 win / 0.5 draw / 0.0 loss), root-relative.
+    wins : f32,
+    // Each edge is keyed by the move that reaches it, not the node: a transposing move
+    // links to an already-explored node from a different parent/move, so the move can't
+    // live on the (possibly shared) node itself without going stale for every parent but
+    // the one that happened to create it.
+    children : Vec<(ChessMove, usize)>,
+    is_explored : bool,
+}
+
+/// Win-rate/visit-count snapshot of a node, handed to a `TreePolicy` so it can decide
+/// which child to descend into without reaching into the arena's internals.
+#[derive(Debug, Clone, Copy)]
+pub struct ChildStats {
+    pub wins: f32,
+    pub visits: u16,
+}
+
+/// Decides which child to descend into during MCTS selection. Kept as a trait so the
+/// exploration/exploitation trade-off can be tuned or swapped without touching `MCTS`.
+pub trait TreePolicy {
+    /// Returns the index *into `children`* of the chosen child.
+    fn choose_child(&self, children: &[ChildStats], parent_visits: u16) -> usize;
+}
+
+/// Classic UCT: `mean_value + c * sqrt(ln(parent_visits + 1) / child_visits)`, computed
+/// on the normalized win rate in `[0, 1]` so `exploration_constant` has a meaningful scale.
+pub struct UctPolicy {
+    pub exploration_constant: f64,
 }
 
-impl MCTSNode {
-    // Calculate UCT value for this node
-    fn uct(&self, parent_visits: u16) -> f32 {
-        if self.visits == 0 {
-            return f32::INFINITY;
+impl Default for UctPolicy {
+    fn default() -> Self {
+        Self { exploration_constant: 1.4 }
+    }
+}
+
+impl TreePolicy for UctPolicy {
+    fn choose_child(&self, children: &[ChildStats], parent_visits: u16) -> usize {
+        children
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
+                self.uct_value(a, parent_visits)
+                    .partial_cmp(&self.uct_value(b, parent_visits))
+                    .unwrap()
+            })
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
+    }
+}
+
+impl UctPolicy {
+    fn uct_value(&self, stats: &ChildStats, parent_visits: u16) -> f64 {
+        if stats.visits == 0 {
+            return f64::INFINITY;
         }
-        let exploitation = self.score as f32  / self.visits as f32;
-        let exploration = 20.0 * ((parent_visits as f32 + 1.0).ln() / self.visits as f32).sqrt();
-        exploitation + exploration
+        let mean_value = stats.wins as f64 / stats.visits as f64;
+        let exploration = self.exploration_constant
+            * ((parent_visits as f64 + 1.0).ln() / stats.visits as f64).sqrt();
+        mean_value + exploration
     }
 }
 
 
+// Fixed-capacity transposition table bounding how much memory the arena's
+// hash -> node-index map can use. Always-replace on collision, same spirit as the
+// negamax search's TT.
+const MCTS_TT_SIZE: usize = 1 << 20; // 1,048,576 slots
+
+struct MctsTranspositionTable {
+    slots: Vec<Option<(u64, usize)>>,
+}
+
+impl MctsTranspositionTable {
+    fn new() -> Self {
+        Self { slots: vec![None; MCTS_TT_SIZE] }
+    }
+
+    fn get(&self, hash: u64) -> Option<usize> {
+        match self.slots[(hash as usize) & (MCTS_TT_SIZE - 1)] {
+            Some((h, node)) if h == hash => Some(node),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, hash: u64, node: usize) {
+        self.slots[(hash as usize) & (MCTS_TT_SIZE - 1)] = Some((hash, node));
+    }
+}
+
 pub struct MCTS {
     nodes: Vec<MCTSNode>,
     nodes_explored: usize,
     pub root_moves : Vec<ChessMove>,
     selected_branch : Vec<usize>,
-    selected_score : i16
+    selected_score : f32,
+    // Maps a board hash to the arena index already holding that position, so distinct
+    // move orders that transpose into the same position share one node instead of each
+    // allocating a duplicate.
+    tt: MctsTranspositionTable
 }
 
 impl MCTS {
     pub fn new(root : GameState) -> Self {
+        let root_hash = root.zobrist();
         let mut mcts = Self {
             nodes: vec![MCTSNode {state : root, ..Default::default()}],
             nodes_explored: 0,
             root_moves: Vec::<ChessMove>::new(),
             selected_branch : Vec::<usize>::new(),
-            selected_score: 0
+            selected_score: 0.0,
+            tt: MctsTranspositionTable::new()
         };
+        mcts.tt.insert(root_hash, 0);
         mcts.root_moves = mcts.explore(0);
         mcts
     }
 
-    fn add_child(&mut self, parent: usize, node : MCTSNode) -> usize {
+    fn add_child(&mut self, parent: usize, mv: ChessMove, node : MCTSNode) -> usize {
         let index = self.nodes.len();
         self.nodes.push(node);
-        self.nodes[parent].children.push(index);
+        self.nodes[parent].children.push((mv, index));
         index
     }
 
@@ -70,96 +232,496 @@ impl MCTS {
         for mv in &mut legal_moves {
             moves_vec.push(mv);
             self.nodes[id].state.make_move(mv);
-            self.add_child(id, MCTSNode {state : self.nodes[id].state.clone(), ..Default::default()});
+            let hash = self.nodes[id].state.zobrist();
+
+            // Link to an existing node if this move transposes into an already-explored
+            // position instead of allocating a duplicate. Either way, `mv` is recorded on
+            // this parent's edge to it, not on the (possibly shared) node.
+            match self.tt.get(hash) {
+                Some(existing) => self.nodes[id].children.push((mv, existing)),
+                None => {
+                    let idx = self.add_child(id, mv, MCTSNode {
+                        state : self.nodes[id].state.clone(),
+                        ..Default::default()
+                    });
+                    self.tt.insert(hash, idx);
+                }
+            }
+
             self.nodes[id].state.undo_last_move();
         }
         self.nodes[id].is_explored = true;
         moves_vec
     }
 
-    // Select the best child using UCT
-    fn select_best_child(&self, id : usize) -> usize {
+    // Select the best child using the supplied tree policy
+    fn select_best_child<T: TreePolicy>(&self, id : usize, tree_policy: &T) -> usize {
+        let children = &self.nodes[id].children;
+        if children.is_empty() {
+            return 0;
+        }
         let visits = self.nodes[id].visits;
-        match self.nodes[id].children
+        let stats: Vec<ChildStats> = children
             .iter()
-            .max_by(|a, b| {
-                self.nodes[**a].uct(visits)
-                    .partial_cmp(&self.nodes[**b].uct(visits))
-                    .unwrap()
-            })
-            {
-                Some(n) => *n,
-                None => 0
-            }
+            .map(|&(_, c)| ChildStats { wins: self.nodes[c].wins, visits: self.nodes[c].visits })
+            .collect();
+        children[tree_policy.choose_child(&stats, visits)].1
     }
 
-    fn select(&mut self) {
+    fn select<T: TreePolicy>(&mut self, tree_policy: &T) {
         self.selected_branch.clear();
         self.selected_branch.push(0);
         let mut current : usize = 0;
         while self.nodes[current].is_explored {
-            current = self.select_best_child(current);
+            current = self.select_best_child(current, tree_policy);
             self.selected_branch.push(current);
         }
     }
 
-    fn expand(&mut self) {
+    fn expand<T: TreePolicy>(&mut self, tree_policy: &T) {
         let leaf : usize = match self.selected_branch.last() {Some(n) => *n, None => 0};
         if self.nodes[leaf].state.last_board().status() == BoardStatus::Ongoing {
             self.explore(leaf);
-            self.selected_branch.push(self.select_best_child(leaf));
+            self.selected_branch.push(self.select_best_child(leaf, tree_policy));
         }
     }
 
-    fn evaluate<E : EvaluateEngine>(&mut self, evaluator : &mut E) {
+    /// Plays a rollout from the selected leaf to a terminal position (or depth limit)
+    /// using `policy`, then converts the result into a root-relative score in [0, 1].
+    fn simulate<E: EvaluateEngine, P: PlayoutPolicy<E>>(&mut self, evaluator: &mut E, policy: &mut P) {
         let leaf : usize = match self.selected_branch.last() {Some(n) => *n, None => 0};
-        let state = &self.nodes[leaf].state;
-        let root_state = &self.nodes[0].state;
-        self.selected_score = (if state.turn() == root_state.turn() {1} else {-1}) * (*evaluator).evaluate(&state).unwrap();
+        let leaf_side = self.nodes[leaf].state.last_board().side_to_move();
+        let root_side = self.nodes[0].state.last_board().side_to_move();
+
+        let mut rollout_state = self.nodes[leaf].state.clone();
+        let outcome = Self::rollout(&mut rollout_state, evaluator, policy);
+
+        // `outcome` is from the perspective of the side to move at the leaf; flip it to
+        // the root's perspective so it can be summed uniformly across the branch, the
+        // same way the previous static-eval score was flipped.
+        let relative = if leaf_side == root_side { outcome.as_score() } else { 1.0 - outcome.as_score() };
+        self.selected_score = relative;
+    }
+
+    /// Plays moves from `state` using `policy` until a terminal position, a draw, or
+    /// `ROLLOUT_DEPTH_LIMIT` plies, returning the outcome from the perspective of the
+    /// side to move in the position the rollout started from.
+    fn rollout<E: EvaluateEngine, P: PlayoutPolicy<E>>(
+        state: &mut GameState,
+        evaluator: &mut E,
+        policy: &mut P,
+    ) -> PlayoutOutcome {
+        let rollout_side = state.last_board().side_to_move();
+
+        for _ in 0..ROLLOUT_DEPTH_LIMIT {
+            let board = state.last_board();
+            match board.status() {
+                BoardStatus::Checkmate => {
+                    let winner = !board.side_to_move();
+                    return if winner == rollout_side { PlayoutOutcome::Win } else { PlayoutOutcome::Loss };
+                }
+                BoardStatus::Stalemate => return PlayoutOutcome::Draw,
+                BoardStatus::Ongoing => {}
+            }
+            if state.is_draw() {
+                return PlayoutOutcome::Draw;
+            }
+
+            let legal_moves: Vec<ChessMove> = MoveGen::new_legal(&board).collect();
+            let mv = policy.select_move(&board, &legal_moves, evaluator);
+            state.make_move(mv);
+        }
+
+        // Depth limit reached: fall back to a static evaluation to produce an outcome.
+        let eval = evaluator.evaluate(state).unwrap();
+        let side_to_move_eval = if state.last_board().side_to_move() == rollout_side { eval } else { -eval };
+        if side_to_move_eval > ROLLOUT_DECISIVE_MARGIN {
+            PlayoutOutcome::Win
+        } else if side_to_move_eval < -ROLLOUT_DECISIVE_MARGIN {
+            PlayoutOutcome::Loss
+        } else {
+            PlayoutOutcome::Draw
+        }
     }
 
     fn backpropagate(&mut self) {
         for node in &mut self.selected_branch {
             self.nodes[*node].visits += 1;
-            self.nodes[*node].score = (self.nodes[*node].score as i32 + self.selected_score as i32).min(i16::MAX as i32 / 2).max(-i16::MAX as i32 / 2)  as i16;
+            self.nodes[*node].wins += self.selected_score;
         }
     }
 
-    pub fn root_scores(&self) -> Vec::<i16> {
-        self.nodes[0].children.iter().map(|n| self.nodes[*n].score).collect()
+    /// Mean win rate (in [0, 1]) of each root child, used to pick the final move.
+    pub fn root_scores(&self) -> Vec::<f32> {
+        self.nodes[0].children
+            .iter()
+            .map(|&(_, n)| {
+                let child = &self.nodes[n];
+                if child.visits == 0 { 0.0 } else { child.wins / child.visits as f32 }
+            })
+            .collect()
     }
 
-    pub fn mcts_step<E : EvaluateEngine>(&mut self, evaluator : &mut E) {
-        self.select();
-        self.expand();
-        self.evaluate::<E>(evaluator);
+    pub fn mcts_step<E: EvaluateEngine, P: PlayoutPolicy<E>, T: TreePolicy>(
+        &mut self,
+        evaluator: &mut E,
+        policy: &mut P,
+        tree_policy: &T,
+    ) {
+        self.select(tree_policy);
+        self.expand(tree_policy);
+        self.simulate::<E, P>(evaluator, policy);
         self.backpropagate();
     }
+
+    /// Promotes the child reached by playing `mv` from the current root to be the new
+    /// root, discarding every node outside that subtree. Returns `None` if `mv` isn't
+    /// one of the current root's explored moves, in which case the caller should fall
+    /// back to building a fresh tree.
+    pub fn reroot(mut self, mv: ChessMove) -> Option<Self> {
+        let slot = self.root_moves.iter().position(|&m| m == mv)?;
+        let new_root_old_idx = self.nodes[0].children[slot].1;
+
+        // Move every node out of the old arena so each one can be relocated exactly once.
+        let mut old_nodes: Vec<Option<MCTSNode>> = self.nodes.drain(..).map(Some).collect();
+        let mut remap = vec![usize::MAX; old_nodes.len()];
+        let mut new_nodes = Vec::new();
+        Self::copy_subtree(new_root_old_idx, &mut old_nodes, &mut remap, &mut new_nodes);
+
+        self.nodes = new_nodes;
+        self.selected_branch.clear();
+        self.selected_score = 0.0;
+
+        // Old arena indices are now invalid, so the hash -> index map has to be rebuilt
+        // from scratch against the relocated nodes.
+        self.tt = MctsTranspositionTable::new();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            self.tt.insert(node.state.zobrist(), idx);
+        }
+
+        // Each child's reaching move lives on the new root's own edges, not on the
+        // (possibly shared, possibly stale) child node, so this is always accurate even
+        // when a child was reached by transposition under its old parent.
+        self.root_moves = if self.nodes[0].is_explored {
+            self.nodes[0].children.iter().map(|&(mv, _)| mv).collect()
+        } else {
+            self.explore(0)
+        };
+        Some(self)
+    }
+
+    // Relocates the subtree rooted at `old_idx` into `new_nodes`, fixing up child indices
+    // and filling in `remap[old_idx]` so shared descendants (DAG edges from the
+    // transposition table) are only copied once.
+    fn copy_subtree(
+        old_idx: usize,
+        old_nodes: &mut Vec<Option<MCTSNode>>,
+        remap: &mut Vec<usize>,
+        new_nodes: &mut Vec<MCTSNode>,
+    ) -> usize {
+        if remap[old_idx] != usize::MAX {
+            return remap[old_idx];
+        }
+
+        let mut node = old_nodes[old_idx].take().expect("node revisited during reroot");
+        let old_children = std::mem::take(&mut node.children);
+
+        let new_idx = new_nodes.len();
+        remap[old_idx] = new_idx;
+        new_nodes.push(node);
+
+        let new_children: Vec<(ChessMove, usize)> = old_children
+            .into_iter()
+            .map(|(mv, c)| (mv, Self::copy_subtree(c, old_nodes, remap, new_nodes)))
+            .collect();
+        new_nodes[new_idx].children = new_children;
+
+        new_idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess::{Board, Square};
+
+    fn find_move(board: &Board, source: Square, dest: Square) -> ChessMove {
+        MoveGen::new_legal(board)
+            .find(|mv| mv.get_source() == source && mv.get_dest() == dest)
+            .expect("move should be legal here")
+    }
+
+    #[test]
+    fn test_transposition_table_always_replaces_on_collision() {
+        let mut tt = MctsTranspositionTable::new();
+        let hash_a: u64 = 0x1234_5678_9abc_def0;
+        // Same low 20 bits as `hash_a` (the slot index), different hash -- a collision.
+        let hash_b = hash_a ^ (MCTS_TT_SIZE as u64);
+        assert_eq!(hash_a & (MCTS_TT_SIZE as u64 - 1), hash_b & (MCTS_TT_SIZE as u64 - 1));
+
+        tt.insert(hash_a, 1);
+        assert_eq!(tt.get(hash_a), Some(1));
+
+        tt.insert(hash_b, 2);
+        assert_eq!(tt.get(hash_b), Some(2), "always-replace: later insert must win the shared slot");
+        assert_eq!(tt.get(hash_a), None, "evicted entry must not be returned for a different hash");
+    }
+
+    #[test]
+    fn test_transposition_table_distinguishes_non_colliding_hashes() {
+        let mut tt = MctsTranspositionTable::new();
+        tt.insert(111, 5);
+        tt.insert(222, 6);
+        assert_eq!(tt.get(111), Some(5));
+        assert_eq!(tt.get(222), Some(6));
+        assert_eq!(tt.get(333), None);
+    }
+
+    // Pins the bug `reroot` used to panic on: a node reached by transposition from two
+    // different paths *within the subtree being kept* (not just from a sibling branch
+    // being discarded), which `copy_subtree` must relocate exactly once rather than
+    // re-copying or choking on the second, already-remapped visit.
+    #[test]
+    fn test_reroot_across_internally_transposing_line() {
+        let mut mcts = MCTS::new(GameState::default());
+
+        let root_board = mcts.nodes[0].state.last_board();
+        let nc3 = find_move(&root_board, Square::B1, Square::C3);
+        let nc3_idx = mcts.nodes[0].children.iter().find(|&&(mv, _)| mv == nc3).unwrap().1;
+
+        mcts.explore(nc3_idx);
+        let nc3_board = mcts.nodes[nc3_idx].state.last_board();
+        let nc6 = find_move(&nc3_board, Square::B8, Square::C6);
+        let nf6 = find_move(&nc3_board, Square::G8, Square::F6);
+        let nc6_idx = mcts.nodes[nc3_idx].children.iter().find(|&&(mv, _)| mv == nc6).unwrap().1;
+        let nf6_idx = mcts.nodes[nc3_idx].children.iter().find(|&&(mv, _)| mv == nf6).unwrap().1;
+
+        // 1. Nc3 Nc6 2. Nf3 Nf6  and  1. Nc3 Nf6 2. Nf3 Nc6  converge on the same position.
+        mcts.explore(nc6_idx);
+        let nc6_board = mcts.nodes[nc6_idx].state.last_board();
+        let w_nf3_via_nc6 = find_move(&nc6_board, Square::G1, Square::F3);
+        let nc6_nf3_idx = mcts.nodes[nc6_idx].children.iter().find(|&&(mv, _)| mv == w_nf3_via_nc6).unwrap().1;
+        mcts.explore(nc6_nf3_idx);
+
+        mcts.explore(nf6_idx);
+        let nf6_board = mcts.nodes[nf6_idx].state.last_board();
+        let w_nf3_via_nf6 = find_move(&nf6_board, Square::G1, Square::F3);
+        let nf6_nf3_idx = mcts.nodes[nf6_idx].children.iter().find(|&&(mv, _)| mv == w_nf3_via_nf6).unwrap().1;
+        mcts.explore(nf6_nf3_idx);
+
+        let nc6_nf3_board = mcts.nodes[nc6_nf3_idx].state.last_board();
+        let b_nf6 = find_move(&nc6_nf3_board, Square::G8, Square::F6);
+        let converged_a = mcts.nodes[nc6_nf3_idx].children.iter().find(|&&(mv, _)| mv == b_nf6).unwrap().1;
+
+        let nf6_nf3_board = mcts.nodes[nf6_nf3_idx].state.last_board();
+        let b_nc6 = find_move(&nf6_nf3_board, Square::B8, Square::C6);
+        let converged_b = mcts.nodes[nf6_nf3_idx].children.iter().find(|&&(mv, _)| mv == b_nc6).unwrap().1;
+
+        assert_eq!(converged_a, converged_b, "test setup didn't actually transpose");
+
+        // Rerooting onto 1. Nc3 carries both paths to the shared node along; this must
+        // not panic, and the new root's own moves must still come back out correctly.
+        let rerooted = mcts.reroot(nc3).expect("Nc3 is a root move");
+        assert!(rerooted.root_moves.contains(&nc6));
+        assert!(rerooted.root_moves.contains(&nf6));
+    }
+}
+
+
+// Fallback think time when the GUI gives us no clock at all (e.g. `go infinite`).
+const DEFAULT_THINK_TIME: Duration = Duration::from_secs(5);
+// Assume this many moves remain on the clock when `moves_to_go` isn't provided.
+const DEFAULT_MOVES_LEFT: u32 = 30;
+// Only bank this fraction of the increment into the budget, keeping a safety margin.
+const INCREMENT_FRACTION: f64 = 0.8;
+// Poll the clock every this many iterations to keep overhead low.
+const TIME_CHECK_BATCH: usize = 128;
+// Hard fallback so a runaway budget (or clock bug) can't search forever.
+const MAX_SEARCH_NODES: usize = 400_000;
+
+pub struct MCTSEngine<E: EvaluateEngine, P: PlayoutPolicy<E>, T: TreePolicy = UctPolicy> {
+    evaluator : E,
+    policy : P,
+    tree_policy : T,
+    // Number of independent root-parallel trees to search with. `1` keeps the original
+    // sequential behavior, including tree reuse across moves; anything higher switches
+    // to spawning that many independent trees per search (see `search_root_parallel`).
+    threads : usize,
+    // Tree kept from the previous search, rooted just after our own last move.
+    // Only used when `threads == 1`, since root-parallel trees are discarded once merged.
+    retained_tree : Option<MCTS>,
+    // Ply (GameState::ply()) at which `retained_tree`'s root sits, used to figure out
+    // which moves were played since then so we can walk the tree forward.
+    retained_ply : usize
 }
 
+impl<E: EvaluateEngine, P: PlayoutPolicy<E>, T: TreePolicy> MCTSEngine<E, P, T> {
+    pub fn new(evaluator : E, policy : P, tree_policy : T, threads : usize) -> Self {
+        Self {evaluator, policy, tree_policy, threads : threads.max(1), retained_tree : None, retained_ply : 0}
+    }
+
+    /// Compute a per-move time budget from the remaining clock and increment.
+    fn compute_time_budget(state: &GameState, time_info: &TimeInfo) -> Duration {
+        if let Some(move_time) = time_info.move_time {
+            return move_time.to_std().unwrap_or(DEFAULT_THINK_TIME);
+        }
+
+        let (remaining, increment) = match state.last_board().side_to_move() {
+            chess::Color::White => (time_info.white_time, time_info.white_increment),
+            chess::Color::Black => (time_info.black_time, time_info.black_increment),
+        };
+
+        let remaining = match remaining.and_then(|d| d.to_std().ok()) {
+            Some(remaining) => remaining,
+            None => return DEFAULT_THINK_TIME,
+        };
+
+        let increment = increment
+            .and_then(|d| d.to_std().ok())
+            .unwrap_or(Duration::ZERO);
+
+        let moves_left = time_info
+            .moves_to_go
+            .map(|n| n as u32)
+            .unwrap_or(DEFAULT_MOVES_LEFT)
+            .max(1);
 
-pub struct MCTSEngine<E : EvaluateEngine> {
-    evaluator : E
+        let budget = remaining / moves_left + increment.mul_f64(INCREMENT_FRACTION);
+        // Never commit more than half the remaining clock to a single move.
+        budget.min(remaining / 2)
+    }
 }
 
-impl<E : EvaluateEngine> MCTSEngine<E>{
-    pub fn new(evaluator : E) -> Self {
-        Self {evaluator : evaluator}
+impl<E, P, T> MCTSEngine<E, P, T>
+where
+    E: EvaluateEngine + Clone + Send,
+    P: PlayoutPolicy<E> + Clone + Send,
+    T: TreePolicy + Sync,
+{
+    /// Runs `self.threads` independent `MCTS` trees from `state` in parallel (via
+    /// crossbeam scoped threads), each searching until `budget` expires, then picks the
+    /// move with the best pooled win rate across all of them.
+    fn search_root_parallel(&mut self, state: &GameState, start: Instant, budget: Duration) -> Option<ChessMove> {
+        let evaluator = &self.evaluator;
+        let policy = &self.policy;
+        let tree_policy = &self.tree_policy;
+        let threads = self.threads;
+
+        let per_thread_stats: Vec<Vec<(ChessMove, u16, f32)>> = cb_thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| {
+                    // Each worker gets its own evaluator/policy clone so rollouts never
+                    // share mutable state, and its own tree so selection needs no locking.
+                    let mut evaluator = evaluator.clone();
+                    let mut policy = policy.clone();
+                    let root_state = state.clone();
+                    scope.spawn(move |_| {
+                        let mut tree = MCTS::new(root_state);
+                        loop {
+                            for _ in 0..TIME_CHECK_BATCH {
+                                tree.mcts_step::<E, P, T>(&mut evaluator, &mut policy, tree_policy);
+                            }
+                            if tree.nodes_explored >= MAX_SEARCH_NODES || Instant::now() - start >= budget {
+                                break;
+                            }
+                        }
+                        tree.nodes[0].children
+                            .iter()
+                            .map(|&(mv, child)| (mv, tree.nodes[child].visits, tree.nodes[child].wins))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("MCTS worker thread panicked"))
+                .collect()
+        })
+        .expect("MCTS worker thread panicked");
+
+        let mut merged: HashMap<ChessMove, (u32, f32)> = HashMap::new();
+        for stats in per_thread_stats {
+            for (mv, visits, wins) in stats {
+                let entry = merged.entry(mv).or_insert((0, 0.0));
+                entry.0 += visits as u32;
+                entry.1 += wins;
+            }
+        }
+
+        let best_move = merged
+            .iter()
+            .max_by(|(_, (visits_a, wins_a)), (_, (visits_b, wins_b))| {
+                let score_a = if *visits_a > 0 { wins_a / *visits_a as f32 } else { 0.0 };
+                let score_b = if *visits_b > 0 { wins_b / *visits_b as f32 } else { 0.0 };
+                score_a.partial_cmp(&score_b).unwrap()
+            })
+            .map(|(&mv, _)| mv);
+
+        log::info!(
+            "Root-parallel MCTS across {} threads finished in {}ms",
+            threads,
+            (Instant::now() - start).as_millis()
+        );
+
+        best_move
     }
 }
 
-impl<E: EvaluateEngine> SearchEngine<E> for MCTSEngine<E> {
+impl<E, P, T> SearchEngine<E> for MCTSEngine<E, P, T>
+where
+    E: EvaluateEngine + Clone + Send,
+    P: PlayoutPolicy<E> + Clone + Send,
+    T: TreePolicy + Sync,
+{
     fn next_move(
         &mut self,
         mut state: GameState,
         time_info: TimeInfo
     ) -> Option<ChessMove> {
         let start = Instant::now();
-                
-        let mut tree_search = MCTS::new(state);
+        let budget = Self::compute_time_budget(&state, &time_info);
+
+        if self.threads > 1 {
+            // Root parallelization needs no locking on a shared tree: every thread grows
+            // its own independent tree from the same position, and we only combine
+            // results once, by summing root-child visit/win statistics. The individual
+            // trees aren't kept around, so there's no tree reuse across moves here.
+            self.retained_tree = None;
+            self.retained_ply = 0;
+            return self.search_root_parallel(&state, start, budget);
+        }
+
+        let mut tree_search = match self.retained_tree.take() {
+            Some(mut tree) => {
+                let mut reusable = true;
+                for mv in state.moves_since(self.retained_ply) {
+                    match tree.reroot(mv) {
+                        Some(advanced) => tree = advanced,
+                        None => {
+                            reusable = false;
+                            break;
+                        }
+                    }
+                }
+                if reusable {
+                    tree
+                } else {
+                    MCTS::new(state.clone())
+                }
+            }
+            None => MCTS::new(state.clone()),
+        };
         tree_search.nodes_explored = 0;
-        for _i in 0..4000 {
-            tree_search.mcts_step::<E>(&mut self.evaluator)
+        loop {
+            for _ in 0..TIME_CHECK_BATCH {
+                tree_search.mcts_step::<E, P, T>(&mut self.evaluator, &mut self.policy, &self.tree_policy);
+            }
+            if tree_search.nodes_explored >= MAX_SEARCH_NODES {
+                break;
+            }
+            if Instant::now() - start >= budget {
+                break;
+            }
         }
         let argmax: Option<usize> = tree_search.root_scores()
                 .iter()
@@ -177,11 +739,18 @@ impl<E: EvaluateEngine> SearchEngine<E> for MCTSEngine<E> {
             (tree_search.nodes_explored as f64 / elapsed.as_secs_f64()).round()
         );
         log::info!("Root scores : {:?}", tree_search.root_scores());
+
+        // Keep the subtree under our chosen move so the next search can pick up where
+        // this one left off, once the opponent's reply is folded back in.
+        self.retained_ply = state.ply() + 1;
+        self.retained_tree = tree_search.reroot(best_move);
+
         Some(best_move)
     }
 
     fn clear_search_state(&mut self) {
-        
+        self.retained_tree = None;
+        self.retained_ply = 0;
     }
 }
 
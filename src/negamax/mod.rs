@@ -2,11 +2,16 @@ mod mv_iter;
 
 use std::{
     i16,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+        Arc, Mutex,
+    },
     time::{Duration, Instant},
 };
 
-use chess::{BoardStatus, ChessMove, MoveGen};
+use chess::{BoardStatus, ChessMove, MoveGen, Square};
 use chrono::{TimeDelta, Utc};
+use crossbeam::thread as cb_thread;
 use ort::Error;
 
 use crate::{
@@ -18,7 +23,7 @@ const TRANSPOTION_TABLE_SIZE: usize = 16_777_216; // 16_777_216 = 2^24
 
 const MATE_THRESHOLD: i16 = 29_000;
 
-const MAX_DEPTH: u16 = 4;
+const MAX_DEPTH: u16 = 64;
 const MAX_PLY: usize = 128;
 const REPETITION_PENALTY: i16 = 50;
 
@@ -26,6 +31,112 @@ const REPETITION_PENALTY: i16 = 50;
 const SIMPLE_ENDGAME_PIECE_COUNT: u32 = 4;
 const ENDGAME_QSEARCH_DEPTH: usize = 8;
 
+// Null-move pruning constants.
+const NULL_MOVE_MIN_DEPTH: u16 = 3;
+const NULL_MOVE_REDUCTION: u16 = 2;
+
+// Late Move Reduction constants.
+const LMR_MIN_DEPTH: u16 = 3;
+const LMR_MIN_MOVE_COUNT: u32 = 4;
+
+// Razoring margins (centipawns), indexed by remaining depth.
+const RAZOR_MARGIN: [i16; 4] = [0, 483, 570, 603];
+
+// Futility pruning margin (centipawns) at depth == 1.
+const FUTILITY_MARGIN: i16 = 150;
+
+// Check extensions: caps how many extra plies a single search path can accumulate from
+// successive checking moves, so a long checking sequence can't blow up the tree.
+const MAX_CHECK_EXTENSIONS: u16 = 16;
+
+// Time management: see `compute_deadline`.
+// Assume this many moves remain on the clock when `moves_to_go` isn't provided.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+// Never commit more than this fraction of the remaining clock to a single move.
+const MAX_TIME_FRACTION: f64 = 0.4;
+// Poll the wall clock every this many nodes to keep the overhead of the check low.
+const NODE_CHECK_INTERVAL: usize = 2048;
+
+/// Stockfish-style depth-skipping tables for Lazy SMP. Helper thread `i` (0-indexed among
+/// the helpers; the main thread never skips) skips iterative-deepening depth `d` when
+/// `skip_depth(i, d)` is true, so different helpers commit full effort to different depths
+/// instead of duplicating the main thread's work.
+const SKIP_SIZE: [u16; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+const SKIP_PHASE: [u16; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+fn skip_depth(helper_idx: usize, depth: u16) -> bool {
+    let i = helper_idx % SKIP_SIZE.len();
+    ((depth + SKIP_PHASE[i]) / SKIP_SIZE[i]) % 2 != 0
+}
+
+/// Per-search stopping condition, shared by the main thread and every Lazy SMP helper.
+/// `stop` is the same flag the helpers already poll to wind down once the main thread is
+/// done; `deadline` (when set) additionally makes `expired` flip it on its own once the
+/// move's time budget runs out, so a long-running search aborts itself instead of relying
+/// on an external caller to notice.
+struct SearchLimits {
+    deadline: Option<Instant>,
+    /// `go nodes N`: every search thread checks its own node count against this (the
+    /// main thread's count is the one reported in the engine's log line), so whichever
+    /// thread reaches it first stops the whole search.
+    max_nodes: Option<u64>,
+    stop: AtomicBool,
+}
+
+impl SearchLimits {
+    /// Checked on (roughly) every node; only actually reads the clock every
+    /// `NODE_CHECK_INTERVAL` nodes, so a caller should pass its own running node count.
+    fn expired(&self, nodes_explored: usize) -> bool {
+        if self.stop.load(Ordering::Relaxed) {
+            return true;
+        }
+        if nodes_explored % NODE_CHECK_INTERVAL != 0 {
+            return false;
+        }
+        if let Some(max_nodes) = self.max_nodes {
+            if nodes_explored as u64 >= max_nodes {
+                self.stop.store(true, Ordering::Relaxed);
+                return true;
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                self.stop.store(true, Ordering::Relaxed);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Computes a per-move time budget from the side-to-move's clock, as an absolute deadline
+/// from `start`. `move_time` overrides everything else. Otherwise the budget is
+/// `time_left / moves_to_go + increment` (or `time_left / DEFAULT_MOVES_TO_GO + increment`
+/// when the GUI didn't send `moves_to_go`), clamped to never commit more than
+/// `MAX_TIME_FRACTION` of the remaining clock to one move. Returns `None` when there's no
+/// clock information at all (e.g. `go infinite`), meaning the search should only be bounded
+/// by `MAX_DEPTH`.
+fn compute_deadline(state: &GameState, time_info: &TimeInfo, start: Instant) -> Option<Instant> {
+    if let Some(move_time) = time_info.move_time {
+        return Some(start + move_time.to_std().unwrap_or(Duration::ZERO));
+    }
+
+    let (remaining, increment) = match state.last_board().side_to_move() {
+        chess::Color::White => (time_info.white_time, time_info.white_increment),
+        chess::Color::Black => (time_info.black_time, time_info.black_increment),
+    };
+
+    let remaining = remaining.and_then(|d| d.to_std().ok())?;
+    let increment = increment.and_then(|d| d.to_std().ok()).unwrap_or(Duration::ZERO);
+
+    let budget = match time_info.moves_to_go {
+        Some(n) if n > 0 => remaining / n as u32 + increment,
+        _ => remaining / DEFAULT_MOVES_TO_GO + increment,
+    };
+
+    Some(start + budget.min(remaining.mul_f64(MAX_TIME_FRACTION)))
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum ResultKind {
     Exact,
@@ -40,6 +151,26 @@ impl Default for ResultKind {
     }
 }
 
+impl ResultKind {
+    fn to_bits(self) -> u64 {
+        match self {
+            ResultKind::Exact => 0,
+            ResultKind::LowerBound => 1,
+            ResultKind::UpperBound => 2,
+            ResultKind::None => 3,
+        }
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        match bits {
+            0 => ResultKind::Exact,
+            1 => ResultKind::LowerBound,
+            2 => ResultKind::UpperBound,
+            _ => ResultKind::None,
+        }
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone)]
 pub struct SearchResult {
     hash: u64,
@@ -47,36 +178,172 @@ pub struct SearchResult {
     score: i16,
     kind: ResultKind,
     best_move: Option<ChessMove>,
+    /// The search generation (see `Negamax::age`) this entry was written during, so
+    /// replacement can prefer overwriting stale entries from earlier searches even when
+    /// they're not shallower.
+    age: u8,
 }
 
-pub struct Negamax<E : EvaluateEngine> {
-    nodes_explored: usize,
-    transposition_table: Vec<SearchResult>,
+impl SearchResult {
+    /// Packs everything but `hash` into a single word, so a slot can be written with one
+    /// atomic store. Layout (low to high bits): score:16, depth:16, kind:2, has_move:1,
+    /// source:6, dest:6, promotion:3 (0 = none, else piece index + 1), age:8.
+    fn pack(&self) -> u64 {
+        let mut data = self.score as u16 as u64;
+        data |= (self.depth as u64) << 16;
+        data |= self.kind.to_bits() << 32;
+        if let Some(mv) = self.best_move {
+            data |= 1u64 << 34;
+            data |= (mv.get_source().to_index() as u64) << 35;
+            data |= (mv.get_dest().to_index() as u64) << 41;
+            let promo_bits = mv.get_promotion().map_or(0, |p| p.to_index() as u64 + 1);
+            data |= promo_bits << 47;
+        }
+        data |= (self.age as u64) << 50;
+        data
+    }
+
+    fn unpack(hash: u64, data: u64) -> Self {
+        let score = (data & 0xFFFF) as u16 as i16;
+        let depth = ((data >> 16) & 0xFFFF) as u16;
+        let kind = ResultKind::from_bits((data >> 32) & 0b11);
+        let best_move = if (data >> 34) & 1 == 1 {
+            let source = Square::new(((data >> 35) & 0x3F) as u8);
+            let dest = Square::new(((data >> 41) & 0x3F) as u8);
+            let promo_bits = (data >> 47) & 0b111;
+            let promotion = if promo_bits == 0 {
+                None
+            } else {
+                Some(chess::ALL_PIECES[(promo_bits - 1) as usize])
+            };
+            Some(ChessMove::new(source, dest, promotion))
+        } else {
+            None
+        };
+        let age = ((data >> 50) & 0xFF) as u8;
+
+        Self {
+            hash,
+            depth,
+            score,
+            kind,
+            best_move,
+            age,
+        }
+    }
+}
+
+/// One lock-free transposition table slot. `data` holds a packed `SearchResult` (see
+/// `SearchResult::pack`) and `key` holds `hash ^ data`, the standard XOR-key trick: a reader
+/// that recomputes `key ^ data` and finds it doesn't match the position hash it's probing
+/// knows it raced a concurrent writer and caught a torn read, and just treats the slot as a
+/// miss rather than risking a corrupted entry. This is what lets every Lazy SMP thread write
+/// into the table without a lock.
+struct TTSlot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Default for TTSlot {
+    fn default() -> Self {
+        Self {
+            key: AtomicU64::new(0),
+            data: AtomicU64::new(0),
+        }
+    }
+}
+
+fn new_transposition_table() -> Vec<TTSlot> {
+    new_transposition_table_sized(TRANSPOTION_TABLE_SIZE)
+}
+
+/// `slots` must be a power of two: `get_tt_entry`/`save_tt_entry` mask the hash into the
+/// table with `len() - 1` rather than a modulo.
+fn new_transposition_table_sized(slots: usize) -> Vec<TTSlot> {
+    (0..slots).map(|_| TTSlot::default()).collect()
+}
 
-    // Move ordering heuristics
+/// Per-thread search scratch state: move-ordering heuristics and the node counter. Every
+/// Lazy SMP worker (the main search thread, plus each helper) gets its own `Worker` so
+/// concurrent threads never contend over killer moves or the history table -- the
+/// transposition table is the only state they actually share.
+struct Worker {
+    nodes_explored: usize,
     killer_moves: [[Option<ChessMove>; 2]; MAX_PLY],
     counter_moves: [Option<ChessMove>; 64],
     history_table: [[i32; 64]; 64],
     history_move_count: u32,
+}
 
-    evaluator : E
+impl Worker {
+    fn new() -> Self {
+        Self {
+            nodes_explored: 0,
+            killer_moves: [[None; 2]; MAX_PLY],
+            counter_moves: [None; 64],
+            history_table: [[0; 64]; 64],
+            history_move_count: 0,
+        }
+    }
 }
 
+impl Default for Worker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+pub struct Negamax<E: EvaluateEngine> {
+    transposition_table: Vec<TTSlot>,
+    worker: Worker,
+    evaluator: Mutex<E>,
+
+    /// Number of Lazy SMP search threads: `1` (the default) runs single-threaded; `n > 1`
+    /// runs the main thread plus `n - 1` depth-staggered helper threads, all sharing
+    /// `transposition_table`.
+    threads: usize,
+
+    /// Bumped once per `next_move` call and stamped onto every entry `save_tt_entry` writes
+    /// during that search (see `SearchResult::age`). Lets replacement prefer overwriting
+    /// entries left over from an earlier search even when they aren't shallower, since
+    /// they're reused across a game's whole "position -> best_move" table rather than
+    /// cleared each move.
+    age: AtomicU8,
+
+    /// The position `next_move` last searched, kept around so `ponder` has something to
+    /// keep searching on opponent time without needing its own `GameState` parameter.
+    last_state: Option<GameState>,
+
+    /// Flipped to wind `ponder`'s Lazy SMP search down early, checked the same way
+    /// `next_move`'s own `SearchLimits::stop` is. Kept behind an `Arc` (rather than a plain
+    /// `AtomicBool`, like the rest of this struct's state) so a caller can clone a handle
+    /// via `ponder_stop_handle` *before* calling `ponder` -- which blocks for as long as it
+    /// keeps searching -- and signal it from another thread once a `stop`/`ponderhit`
+    /// arrives, the usual way a UCI loop ends pondering.
+    ponder_stop: Arc<AtomicBool>,
+}
 
-impl<E: EvaluateEngine> SearchEngine<E> for Negamax<E> {
-    fn next_move(&mut self, mut state: GameState, _time_info: TimeInfo) -> Option<ChessMove> {
-        self.nodes_explored = 0;
+impl<E: EvaluateEngine + Send> SearchEngine<E> for Negamax<E> {
+    fn next_move(&mut self, mut state: GameState, time_info: TimeInfo) -> Option<ChessMove> {
         let start = Utc::now();
+        let wall_clock_start = Instant::now();
         let mut elapsed = TimeDelta::zero();
 
-        // Clear killer moves for new search
-        for ply in 0..MAX_PLY {
-            self.killer_moves[ply] = [None; 2];
-        }
+        // Bump the search generation so `save_tt_entry` stamps everything written during
+        // this search with an age newer than whatever's already in the table.
+        self.age.fetch_add(1, Ordering::Relaxed);
+        self.last_state = Some(state.clone());
+
+        // Pull the main thread's scratch state out into a local so it can be driven
+        // through `&self` (shared with the helper threads below) without aliasing
+        // `self.worker`; it's put back before returning.
+        let mut worker = std::mem::take(&mut self.worker);
+        worker.nodes_explored = 0;
+        // Clear killer moves for new search (history/counter moves persist across calls).
+        worker.killer_moves = [[None; 2]; MAX_PLY];
 
         let board = state.last_board();
-        let board_hash = board.get_hash();
+        let board_hash = state.zobrist();
         let mut best_move = None;
         let mut last_score = 0;
 
@@ -94,63 +361,135 @@ impl<E: EvaluateEngine> SearchEngine<E> for Negamax<E> {
                 best_move = Some(mv);
             }
         }
-        let mut last_depth = 0;
-        for curr_depth in start_depth..=MAX_DEPTH {
-            last_depth = curr_depth;
-            let mut window = 32;
-            let mut alpha_orig = last_score - window;
-            let mut beta = last_score + window;
-
-            loop {
-                let mv_iter = StagedMoveIterator::new(
-                    board,
-                    best_move,
-                    &self.killer_moves[0],
-                    None, // No counter move at root
-                    &self.history_table,
-                );
 
-                let mut best_score = -i16::MAX;
-
-                let mut alpha = alpha_orig;
-
-                for mv in mv_iter {
-                    let repetition_count = state.make_move(mv);
-                    let score = if repetition_count >= 3 {
-                        0
-                    } else {
-                        -self.search_eval(&mut state, -beta, -alpha, curr_depth - 1, 1)
-                    };
+        // Helpers race alongside the main thread from the same position, staggering the
+        // depths they commit to via `skip_depth` so they seed the shared transposition
+        // table with entries the main thread's own searches then reuse. `limits` tells
+        // them (and the main thread's own recursive calls) to wind down once the main
+        // thread below has finished its iterative deepening or run out of time.
+        let limits = SearchLimits {
+            deadline: compute_deadline(&state, &time_info, wall_clock_start),
+            max_nodes: time_info.max_nodes,
+            stop: AtomicBool::new(false),
+        };
+        let helper_count = self.threads.saturating_sub(1);
+        let max_depth = time_info.max_depth.unwrap_or(MAX_DEPTH).min(MAX_DEPTH);
+        let search_moves = &time_info.search_moves;
+
+        let (final_worker, last_depth) = cb_thread::scope(|scope| {
+            for helper_idx in 0..helper_count {
+                let neg = &*self;
+                let mut helper_state = state.clone();
+                let limits = &limits;
+                scope.spawn(move |_| {
+                    let mut helper_worker = Worker::new();
+                    for depth in 1..=max_depth {
+                        if limits.expired(helper_worker.nodes_explored) {
+                            break;
+                        }
+                        if skip_depth(helper_idx, depth) {
+                            continue;
+                        }
+                        neg.search_root(&mut helper_worker, &mut helper_state, board, depth, limits);
+                    }
+                });
+            }
 
-                    state.undo_last_move();
+            let mut last_depth = 0;
+            for curr_depth in start_depth..=max_depth {
+                if limits.expired(worker.nodes_explored) {
+                    break;
+                }
 
-                    if score > best_score {
-                        best_score = score;
-                        best_move = Some(mv)
+                // A depth that gets cut short by the deadline produces a best move/score
+                // biased toward whichever root moves happened to be searched first, not
+                // the actual best one -- never trust it. Snapshot so it can be rolled back.
+                let depth_start_best_move = best_move;
+                let depth_start_score = last_score;
+
+                let mut window = 32;
+                let mut alpha_orig = last_score - window;
+                let mut beta = last_score + window;
+                let mut aborted = false;
+
+                loop {
+                    let mv_iter = StagedMoveIterator::new(
+                        board,
+                        best_move,
+                        &worker.killer_moves[0],
+                        None, // No counter move at root
+                        &worker.history_table,
+                    )
+                    .filter(|mv| search_moves.as_ref().map_or(true, |moves| moves.contains(mv)));
+
+                    let mut best_score = -i16::MAX;
+
+                    let mut alpha = alpha_orig;
+
+                    for mv in mv_iter {
+                        if limits.expired(worker.nodes_explored) {
+                            aborted = true;
+                            break;
+                        }
+
+                        let repetition_count = state.make_move(mv);
+                        let score = if repetition_count >= 3 {
+                            0
+                        } else {
+                            -self.search_eval(&mut worker, &mut state, -beta, -alpha, curr_depth - 1, 1, 0, &limits)
+                        };
+
+                        state.undo_last_move();
+
+                        if score > best_score {
+                            best_score = score;
+                            best_move = Some(mv)
+                        }
+                        alpha = alpha.max(best_score);
+                        if alpha >= beta {
+                            break;
+                        }
+                    }
+                    // A pass cut short mid-iteration never finished comparing all root
+                    // moves, so its best_score can't be trusted to decide whether to widen
+                    // the aspiration window -- bail out of both loops without touching it.
+                    if aborted {
+                        break;
                     }
-                    alpha = alpha.max(best_score);
-                    if alpha >= beta {
+                    if best_score <= alpha_orig {
+                        alpha_orig = alpha_orig.saturating_sub(window);
+                    } else if best_score >= beta {
+                        beta = beta.saturating_add(window);
+                    } else {
+                        last_score = best_score;
                         break;
                     }
+                    window *= 2;
                 }
-                if best_score <= alpha_orig {
-                    alpha_orig = alpha_orig.saturating_sub(window);
-                } else if best_score >= beta {
-                    beta = beta.saturating_add(window);
-                } else {
-                    last_score = best_score;
+                elapsed = Utc::now() - start;
+
+                if aborted || limits.stop.load(Ordering::Relaxed) {
+                    best_move = depth_start_best_move;
+                    last_score = depth_start_score;
                     break;
                 }
-                window *= 2;
+                last_depth = curr_depth;
             }
-            elapsed = (Utc::now() - start);
-        }
+
+            // The main thread is done; helpers poll `limits` and unwind on their own.
+            limits.stop.store(true, Ordering::Relaxed);
+
+            (worker, last_depth)
+        })
+        .expect("Lazy SMP worker thread panicked");
+
+        self.worker = final_worker;
 
         log::info!(
             "Nodes explored: {} in {}ms. {:.0} NPS up to depth {}",
-            self.nodes_explored,
+            self.worker.nodes_explored,
             elapsed.num_milliseconds(),
-            (self.nodes_explored as f64 / elapsed.as_seconds_f64()).round(),
+            (self.worker.nodes_explored as f64 / elapsed.as_seconds_f64()).round(),
             last_depth
         );
         log::info!("Best move: {:?}, Best score: {}", best_move.map(|x| x.to_string()), last_score);
@@ -159,63 +498,207 @@ impl<E: EvaluateEngine> SearchEngine<E> for Negamax<E> {
     }
 
     fn clear_search_state(&mut self) {
-        self.killer_moves = [[None; 2]; MAX_PLY];
-        self.counter_moves = [None; 64];
-        self.history_table = [[0; 64]; 64];
-        self.history_move_count = 0;
+        self.worker = Worker::new();
         // Clear TT to avoid using moves from previous positions
-        self.transposition_table = vec![SearchResult::default(); TRANSPOTION_TABLE_SIZE];
+        self.transposition_table = new_transposition_table();
+        self.last_state = None;
+    }
+
+    /// Keeps searching the position `next_move` was last asked about, the same Lazy SMP way
+    /// `next_move` itself does (sharing `transposition_table` across `self.threads` workers),
+    /// but with no time/node limit -- only `ponder_stop` (see `ponder_stop_handle`) or
+    /// hitting `MAX_DEPTH` ends it. Blocks until it stops, so a caller wanting the engine to
+    /// keep responding to other commands while pondering needs to run this on its own
+    /// thread. A no-op if `next_move` hasn't been called yet.
+    fn ponder(&mut self) {
+        let Some(state) = self.last_state.clone() else {
+            return;
+        };
+
+        self.ponder_stop.store(false, Ordering::Relaxed);
+        let board = state.last_board();
+        let limits = SearchLimits {
+            deadline: None,
+            max_nodes: None,
+            stop: AtomicBool::new(false),
+        };
+
+        let mut worker = std::mem::take(&mut self.worker);
+        cb_thread::scope(|scope| {
+            for helper_idx in 0..self.threads.saturating_sub(1) {
+                let neg = &*self;
+                let mut helper_state = state.clone();
+                let limits = &limits;
+                scope.spawn(move |_| {
+                    let mut helper_worker = Worker::new();
+                    for depth in 1..=MAX_DEPTH {
+                        if neg.ponder_stop.load(Ordering::Relaxed) || limits.expired(helper_worker.nodes_explored) {
+                            break;
+                        }
+                        if skip_depth(helper_idx, depth) {
+                            continue;
+                        }
+                        neg.search_root(&mut helper_worker, &mut helper_state, board, depth, limits);
+                    }
+                });
+            }
+
+            let mut pondering_state = state.clone();
+            for depth in 1..=MAX_DEPTH {
+                if self.ponder_stop.load(Ordering::Relaxed) || limits.expired(worker.nodes_explored) {
+                    break;
+                }
+                self.search_root(&mut worker, &mut pondering_state, board, depth, &limits);
+            }
+            limits.stop.store(true, Ordering::Relaxed);
+        })
+        .expect("Lazy SMP worker thread panicked");
+        self.worker = worker;
+    }
+
+    /// UCI `Hash` option: resizes the transposition table to the largest power-of-two
+    /// slot count that fits in `megabytes` MB, clearing its contents.
+    fn set_hash_size_mb(&mut self, megabytes: usize) {
+        let slot_bytes = std::mem::size_of::<TTSlot>();
+        let requested_slots = (megabytes.max(1) * 1024 * 1024 / slot_bytes).max(1);
+        let slots = if requested_slots.is_power_of_two() {
+            requested_slots
+        } else {
+            (requested_slots.next_power_of_two() >> 1).max(1)
+        };
+        self.transposition_table = new_transposition_table_sized(slots);
+    }
+
+    /// UCI `Threads` option: sets the number of Lazy SMP search threads (see `threads`).
+    fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
     }
 }
 
-impl<E : EvaluateEngine> Negamax<E> {
-    pub fn new(evaluator : E) -> Self {
+impl<E: EvaluateEngine> Negamax<E> {
+    pub fn new(evaluator: E) -> Self {
+        Self::with_threads(evaluator, 1)
+    }
+
+    /// Same as `new`, but with `threads` Lazy SMP search threads (see the `threads` field).
+    pub fn with_threads(evaluator: E, threads: usize) -> Self {
         Self {
-            nodes_explored: 0,
-            transposition_table: vec![SearchResult::default(); TRANSPOTION_TABLE_SIZE],
-            killer_moves: [[None; 2]; MAX_PLY],
-            counter_moves: [None; 64],
-            history_table: [[0; 64]; 64],
-            history_move_count: 0,
-            evaluator : evaluator
+            transposition_table: new_transposition_table(),
+            worker: Worker::new(),
+            evaluator: Mutex::new(evaluator),
+            threads: threads.max(1),
+            age: AtomicU8::new(0),
+            last_state: None,
+            ponder_stop: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// A clone of the flag that ends an in-progress `ponder` call. Grab this *before*
+    /// calling `ponder` (which blocks the calling thread for as long as it keeps searching)
+    /// so another thread can signal it once a `stop`/`ponderhit` arrives.
+    pub fn ponder_stop_handle(&self) -> Arc<AtomicBool> {
+        self.ponder_stop.clone()
+    }
+
     fn get_tt_entry(&self, hash: u64) -> Option<SearchResult> {
-        let transpo_idx = (hash as usize) & (TRANSPOTION_TABLE_SIZE - 1);
+        let transpo_idx = (hash as usize) & (self.transposition_table.len() - 1);
+        let slot = &self.transposition_table[transpo_idx];
+
+        let key = slot.key.load(Ordering::Relaxed);
+        let data = slot.data.load(Ordering::Relaxed);
+        if key ^ data != hash {
+            return None;
+        }
 
-        let entry = self.transposition_table[transpo_idx];
-        if entry.hash == hash && entry.kind != ResultKind::None {
+        let entry = SearchResult::unpack(hash, data);
+        if entry.kind != ResultKind::None {
             return Some(entry);
         }
         None
     }
 
-    fn save_tt_entry(&mut self, search_result: SearchResult) {
-        let transpo_idx = (search_result.hash as usize) & (TRANSPOTION_TABLE_SIZE - 1);
-        self.transposition_table[transpo_idx] = search_result;
+    /// Stamps `search_result` with the current search generation (see `age`) before writing
+    /// it into its slot.
+    fn save_tt_entry(&self, mut search_result: SearchResult) {
+        search_result.age = self.age.load(Ordering::Relaxed);
+
+        let transpo_idx = (search_result.hash as usize) & (self.transposition_table.len() - 1);
+        let slot = &self.transposition_table[transpo_idx];
+
+        let data = search_result.pack();
+        slot.data.store(data, Ordering::Relaxed);
+        slot.key.store(search_result.hash ^ data, Ordering::Relaxed);
+    }
+
+    fn evaluate(&self, state: &GameState) -> i16 {
+        self.evaluator.lock().unwrap().evaluate(state).unwrap()
     }
+
+    /// A helper thread's root move loop: a plain full-window negamax call per legal move
+    /// (no aspiration window, no best-move bookkeeping) since a helper's only job is to
+    /// reach new transposition table entries for the main thread to reuse, not to report
+    /// its own best move.
+    fn search_root(
+        &self,
+        worker: &mut Worker,
+        state: &mut GameState,
+        board: chess::Board,
+        depth: u16,
+        limits: &SearchLimits,
+    ) {
+        let mv_iter = StagedMoveIterator::new(board, None, &worker.killer_moves[0], None, &worker.history_table);
+
+        let mut alpha = -i16::MAX;
+        let beta = i16::MAX;
+
+        for mv in mv_iter {
+            if limits.expired(worker.nodes_explored) {
+                break;
+            }
+            let repetition_count = state.make_move(mv);
+            let score = if repetition_count >= 3 {
+                0
+            } else {
+                -self.search_eval(worker, state, -beta, -alpha, depth - 1, 1, 0, limits)
+            };
+            state.undo_last_move();
+
+            alpha = alpha.max(score);
+        }
+    }
+
     pub fn search_eval(
-        &mut self,
+        &self,
+        worker: &mut Worker,
         state: &mut GameState,
         mut alpha: i16,
         beta: i16,
         depth: u16,
         ply: usize,
+        extensions: u16,
+        limits: &SearchLimits,
     ) -> i16 {
-        self.nodes_explored += 1;
+        worker.nodes_explored += 1;
+        if limits.expired(worker.nodes_explored) {
+            return alpha;
+        }
 
         let board = state.last_board();
-        let board_hash = board.get_hash();
+        let board_hash = state.zobrist();
 
         let entry = self.get_tt_entry(board_hash);
 
         let mut best_score = -i16::MAX;
         let mut best_move = None;
 
+        let current_age = self.age.load(Ordering::Relaxed);
         let mut replace_entry = entry.is_none();
         if let Some(entry) = entry {
-            replace_entry |= entry.depth <= depth;
+            // Depth-preferred-but-age-aware: normally only overwrite an entry with one from
+            // an equal-or-deeper search, but an entry left over from an earlier game position
+            // (a stale age) is worth overwriting regardless of its depth, since it'll never
+            // be probed again on purpose.
+            replace_entry |= entry.depth <= depth || entry.age != current_age;
 
             if entry.depth >= depth {
                 match entry.kind {
@@ -231,12 +714,12 @@ impl<E : EvaluateEngine> Negamax<E> {
         }
 
         if depth == 0 {
-            return self.quiescence(state, alpha, beta, ply);
+            return self.quiescence(worker, state, alpha, beta, ply, limits);
         }
 
         match board.status() {
             BoardStatus::Stalemate | BoardStatus::Checkmate => {
-                return self.evaluator.evaluate(state).unwrap();
+                return self.evaluate(state);
             }
             BoardStatus::Ongoing => (),
         }
@@ -244,37 +727,142 @@ impl<E : EvaluateEngine> Negamax<E> {
         // Store so that we can decide later the kind of bound we want to store in the transposition table
         let alpha_orig = alpha.clone();
 
+        let is_pv = (beta as i32 - alpha as i32) > 1;
+        let in_check = board.checkers().popcnt() > 0;
+
+        // Razoring: at shallow non-PV nodes so far below alpha that it's very unlikely
+        // any move here raises it, don't bother with a full search -- a quiescence call
+        // is cheap confirmation that the position really is that bad.
+        if !is_pv && !in_check && (depth as usize) < RAZOR_MARGIN.len() {
+            let static_eval = self.evaluate(state);
+            if static_eval + RAZOR_MARGIN[depth as usize] <= alpha {
+                return self.quiescence(worker, state, alpha, beta, ply, limits);
+            }
+        }
+
+        // Null-move pruning: at non-PV nodes (narrow window) deep enough to trust it, pass
+        // the turn and search at a reduced depth. If the opponent still can't avoid a
+        // fail-high even with a free move, the position is so strong that the real search can't
+        // possibly be worse, so prune here. Guarded against zugzwang by skipping in simple
+        // endgames and when the side to move has no non-pawn material.
+        if !is_pv
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && !in_check
+            && board.combined().popcnt() > SIMPLE_ENDGAME_PIECE_COUNT
+            && self.has_non_pawn_material(&board, board.side_to_move())
+        {
+            let prev = state.make_null_move();
+            let null_score = -self.search_eval(
+                worker,
+                state,
+                -beta,
+                -beta + 1,
+                depth - 1 - NULL_MOVE_REDUCTION,
+                ply + 1,
+                extensions,
+                limits,
+            );
+            state.undo_null_move(prev);
+
+            if null_score >= beta {
+                return beta;
+            }
+        }
+
+        // Futility pruning: at depth-1 non-PV nodes already well below alpha, quiet moves
+        // are very unlikely to recover, so skip searching them entirely and only look at
+        // captures, promotions, and checks.
+        let futility_active =
+            !is_pv && depth == 1 && !in_check && self.evaluate(state) + FUTILITY_MARGIN <= alpha;
+
         // Determine counter move (response to opponent's last move)
         let counter_move = None; // Will be implemented in Phase 4
 
         let mv_iter = StagedMoveIterator::new(
             board,
             best_move,
-            &self.killer_moves[ply],
+            &worker.killer_moves[ply],
             counter_move,
-            &self.history_table,
+            &worker.history_table,
         );
 
         let mut move_count = 0;
+        let mut aborted = false;
 
         for mv in mv_iter {
+            // Once the search has been aborted (deadline/node limit/external stop), every
+            // further recursive call below just short-circuits and returns `alpha` --
+            // scores that look like real search results but aren't. Stop folding those
+            // into `best_score`/`best_move` and, below, don't let them corrupt the shared
+            // TT with an entry that looks like a completed search at `depth`.
+            if limits.expired(worker.nodes_explored) {
+                aborted = true;
+                break;
+            }
+
             let repetition_count = state.make_move(mv);
 
             move_count += 1;
 
+            // Check extension: a move that gives check gets searched one ply deeper
+            // (forced replies need the extra look), bounded so a long checking sequence
+            // can't blow up the tree.
+            let gives_check = state.last_board().checkers().popcnt() > 0;
+            let extend = gives_check && extensions < MAX_CHECK_EXTENSIONS;
+            let child_depth = if extend { depth } else { depth - 1 };
+            let child_extensions = if extend { extensions + 1 } else { extensions };
+
+            if futility_active
+                && move_count > 1
+                && !gives_check
+                && mv.get_promotion().is_none()
+                && self.is_quiet_move(mv, &board)
+            {
+                state.undo_last_move();
+                move_count -= 1;
+                continue;
+            }
+
             let score = if repetition_count >= 3 {
                 0
             } else if move_count == 1 {
                 // First move: full window (PV node)
-                -self.search_eval(state, -beta, -alpha, depth - 1, ply + 1)
+                -self.search_eval(worker, state, -beta, -alpha, child_depth, ply + 1, child_extensions, limits)
             } else {
+                // Late Move Reductions: quiet moves beyond the first few, searched deep
+                // enough to trust it, get a shallower null-window probe first. A move
+                // that earned a check extension is searched at full depth instead.
+                let reduction = if !extend
+                    && depth >= LMR_MIN_DEPTH
+                    && move_count > LMR_MIN_MOVE_COUNT
+                    && self.is_quiet_move(mv, &board)
+                {
+                    self.lmr_reduction(depth, move_count)
+                } else {
+                    0
+                };
+
                 // Null window search
-                let mut score =
-                    -self.search_eval(state, -alpha - 1, -alpha, depth - 1, ply + 1);
+                let mut score = -self.search_eval(
+                    worker,
+                    state,
+                    -alpha - 1,
+                    -alpha,
+                    child_depth - reduction,
+                    ply + 1,
+                    child_extensions,
+                    limits,
+                );
+
+                if reduction > 0 && score > alpha {
+                    // A reduced search can't be trusted as a real fail-high; verify at
+                    // full depth before deciding whether it's worth a full re-search.
+                    score = -self.search_eval(worker, state, -alpha - 1, -alpha, child_depth, ply + 1, child_extensions, limits);
+                }
 
                 // Re-search if it beat alpha
                 if score > alpha && score < beta {
-                    score = -self.search_eval(state, -beta, -alpha, depth - 1, ply + 1);
+                    score = -self.search_eval(worker, state, -beta, -alpha, child_depth, ply + 1, child_extensions, limits);
                 }
                 score
             };
@@ -289,7 +877,7 @@ impl<E : EvaluateEngine> Negamax<E> {
 
             if alpha >= beta {
                 // Beta cutoff: update move ordering heuristics
-                self.update_move_ordering(mv, &board, depth, ply, None);
+                self.update_move_ordering(worker, mv, &board, depth, ply, None);
                 break;
             }
         }
@@ -304,28 +892,34 @@ impl<E : EvaluateEngine> Negamax<E> {
             ResultKind::Exact
         };
 
-        if replace_entry {
+        if replace_entry && !aborted {
             self.save_tt_entry(SearchResult {
                 hash: board_hash,
                 depth,
                 score: best_score,
                 kind,
                 best_move,
+                age: 0, // stamped with the current generation by save_tt_entry
             });
         }
         best_score
     }
 
     fn quiescence(
-        &mut self,
+        &self,
+        worker: &mut Worker,
         state: &mut GameState,
         mut alpha: i16,
         beta: i16,
         ply: usize,
+        limits: &SearchLimits,
     ) -> i16 {
-        self.nodes_explored += 1;
+        worker.nodes_explored += 1;
+        if limits.expired(worker.nodes_explored) {
+            return alpha;
+        }
 
-        let stand_pat = self.evaluator.evaluate(state).unwrap();
+        let stand_pat = self.evaluate(state);
 
         if stand_pat >= beta {
             return beta;
@@ -340,16 +934,10 @@ impl<E : EvaluateEngine> Negamax<E> {
         let total_pieces = board.combined().popcnt();
         let is_simple_endgame = total_pieces <= SIMPLE_ENDGAME_PIECE_COUNT;
 
-        // Generate captures
-        let mut captures: Vec<ChessMove> = MoveGen::new_legal(&board)
-            .filter(|mv| board.piece_on(mv.get_dest()).is_some())
-            .collect();
-
-        // Filter out losing captures using SEE
-        captures.retain(|&mv| self.see(&board, mv, -100));
-
-        // Sort by MVV-LVV descending
-        captures.sort_unstable_by_key(|&mv| -self.mvv_lvv_score(mv, &board));
+        // Generate captures via the same staged, mask-restricted generator the main search
+        // uses (already MVV-LVA/SEE ordered), rather than a separate MoveGen scan plus its
+        // own Vec/sort/retain dance.
+        let captures = StagedMoveIterator::new_captures_only(board, None, &worker.history_table);
 
         // In simple endgames, also consider checks and King moves
         let mut extended_moves = Vec::new();
@@ -377,6 +965,12 @@ impl<E : EvaluateEngine> Negamax<E> {
 
         // First try captures
         for mv in captures {
+            // Filter out losing captures using SEE (the iterator only splits on SEE >= 0
+            // vs. < 0, so a capture losing less than 100cp is still worth trying here).
+            if !self.see(&board, mv, -100) {
+                continue;
+            }
+
             // Delta pruning: if even best-case capture can't raise alpha, skip
             let optimistic_score = stand_pat + self.mvv_lvv_score(mv, &board) + 200;
             if optimistic_score < alpha {
@@ -387,7 +981,7 @@ impl<E : EvaluateEngine> Negamax<E> {
             let score = if repetition_count >= 3 {
                 0
             } else {
-                -self.quiescence(state, -beta, -alpha, ply + 1)
+                -self.quiescence(worker, state, -beta, -alpha, ply + 1, limits)
             };
             state.undo_last_move();
 
@@ -405,7 +999,7 @@ impl<E : EvaluateEngine> Negamax<E> {
             let score = if repetition_count >= 3 {
                 0
             } else {
-                -self.quiescence(state, -beta, -alpha, ply + 1)
+                -self.quiescence(worker, state, -beta, -alpha, ply + 1, limits)
             };
             state.undo_last_move();
 
@@ -455,18 +1049,40 @@ impl<E : EvaluateEngine> Negamax<E> {
         board.piece_on(mv.get_dest()).is_none()
     }
 
+    /// Whether `color` has any knight/bishop/rook/queen left, used to disable null-move
+    /// pruning in piece-less endgames where a side-to-move with only pawns (or just a
+    /// king) is especially prone to zugzwang.
+    #[inline(always)]
+    fn has_non_pawn_material(&self, board: &chess::Board, color: chess::Color) -> bool {
+        use chess::Piece;
+
+        let non_pawn_king =
+            board.pieces(Piece::Knight) | board.pieces(Piece::Bishop) | board.pieces(Piece::Rook) | board.pieces(Piece::Queen);
+        (non_pawn_king & board.color_combined(color)).popcnt() > 0
+    }
+
+    /// Late Move Reduction: how much to shave off `depth` for the `move_count`-th move
+    /// searched there, shaped like `log(depth) * log(move_count)` (the classic LMR
+    /// formula), clamped so it never reduces all the way past quiescence.
+    #[inline(always)]
+    fn lmr_reduction(&self, depth: u16, move_count: u32) -> u16 {
+        let reduction = ((depth as f64).ln() * (move_count as f64).ln() / 2.0) as u16;
+        reduction.min(depth - 1)
+    }
+
     #[inline(never)]
-    fn age_history(&mut self) {
+    fn age_history(&self, worker: &mut Worker) {
         for i in 0..64 {
             for j in 0..64 {
-                self.history_table[i][j] /= 2;
+                worker.history_table[i][j] /= 2;
             }
         }
     }
 
     #[inline(always)]
     fn update_move_ordering(
-        &mut self,
+        &self,
+        worker: &mut Worker,
         mv: ChessMove,
         board: &chess::Board,
         depth: u16,
@@ -476,61 +1092,35 @@ impl<E : EvaluateEngine> Negamax<E> {
         // Only update for quiet moves (captures ordered by MVV-LVV/SEE)
         if self.is_quiet_move(mv, board) {
             // 1. Update killer moves (shift down if new)
-            if self.killer_moves[ply][0] != Some(mv) {
-                self.killer_moves[ply][1] = self.killer_moves[ply][0];
-                self.killer_moves[ply][0] = Some(mv);
+            if worker.killer_moves[ply][0] != Some(mv) {
+                worker.killer_moves[ply][1] = worker.killer_moves[ply][0];
+                worker.killer_moves[ply][0] = Some(mv);
             }
 
             // 2. Update history heuristic (bonus = depth^2)
             let bonus = (depth as i32) * (depth as i32);
             let from = mv.get_source().to_index();
             let to = mv.get_dest().to_index();
-            self.history_table[from][to] += bonus;
+            worker.history_table[from][to] += bonus;
 
             // 3. Age history periodically
-            self.history_move_count += 1;
-            if self.history_move_count >= 1024 {
-                self.age_history();
-                self.history_move_count = 0;
+            worker.history_move_count += 1;
+            if worker.history_move_count >= 1024 {
+                self.age_history(worker);
+                worker.history_move_count = 0;
             }
 
             // 4. Update counter move
             if let Some(last_to) = last_move_to_square {
-                self.counter_moves[last_to] = Some(mv);
+                worker.counter_moves[last_to] = Some(mv);
             }
         }
     }
 
-    #[inline]
+    /// Static Exchange Evaluation, delegating to `mv_iter::see` -- the move-ordering code
+    /// in `mv_iter` needs the identical algorithm (and already gets en passant right), so
+    /// this just calls through rather than keeping a second, divergent copy here.
     fn see(&self, board: &chess::Board, mv: ChessMove, threshold: i16) -> bool {
-        // Simplified Static Exchange Evaluation
-        // Returns true if capture wins at least 'threshold' material
-
-        // Get piece values
-        const PIECE_VALUES: [i16; 6] = [100, 320, 330, 500, 900, 0]; // P, N, B, R, Q, K
-
-        let victim = board.piece_on(mv.get_dest());
-        let attacker = board.piece_on(mv.get_source());
-
-        if victim.is_none() {
-            // Not a capture
-            return threshold <= 0;
-        }
-
-        if attacker.is_none() {
-            return false;
-        }
-
-        let victim_value = PIECE_VALUES[victim.unwrap().to_index()];
-        let attacker_value = PIECE_VALUES[attacker.unwrap().to_index()];
-
-        // Simple heuristic: capture is good if we gain material
-        // victim_value - attacker_value >= threshold
-        // This is a simplification - proper SEE would simulate full exchange
-        let simple_gain = victim_value - attacker_value;
-
-        // For now, just use this simple heuristic
-        // A proper SEE implementation would require more complex attack detection
-        simple_gain >= threshold
+        mv_iter::see(board, mv, threshold)
     }
 }
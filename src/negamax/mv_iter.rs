@@ -1,4 +1,4 @@
-use chess::{Board, ChessMove, MoveGen};
+use chess::{Board, ChessMove, MoveGen, Square};
 
 pub struct StagedMoveIterator {
     board: Board,
@@ -30,6 +30,10 @@ pub struct StagedMoveIterator {
 
     // Reference to history table (raw pointer for efficiency)
     history_table_ptr: *const [[i32; 64]; 64],
+
+    // When set (see `new_captures_only`), `GoodCaptures` jumps straight to `BadCaptures`
+    // once exhausted instead of continuing on through killers/counter/quiet moves.
+    captures_only: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -70,9 +74,23 @@ impl StagedMoveIterator {
             bad_captures: Vec::with_capacity(32),
             bad_captures_idx: 0,
             history_table_ptr: history_table as *const _,
+            captures_only: false,
         }
     }
 
+    /// Like `new`, but for quiescence search: only walks TTMove -> GenerateCaptures ->
+    /// GoodCaptures -> BadCaptures -> Done, skipping killer/counter/quiet generation
+    /// entirely since quiescence never looks at quiet moves.
+    pub fn new_captures_only(
+        board: Board,
+        tt_move: Option<ChessMove>,
+        history_table: &[[i32; 64]; 64],
+    ) -> Self {
+        let mut iter = Self::new(board, tt_move, &[None, None], None, history_table);
+        iter.captures_only = true;
+        iter
+    }
+
     fn mvv_lvv_score(mv: ChessMove, board: &Board) -> i16 {
         const PIECE_VALUES: [i16; 6] = [100, 320, 330, 500, 900, 0]; // P, N, B, R, Q, K
 
@@ -95,18 +113,25 @@ impl StagedMoveIterator {
         self.good_captures.clear();
         self.bad_captures.clear();
 
-        // Generate all captures
-        let captures = MoveGen::new_legal(&self.board);
+        // Restrict the move generator to captures by masking it down to the opponent's
+        // occupancy, so non-captures are never enumerated in the first place -- plus the
+        // en-passant destination square, which the mask trick otherwise misses since it's
+        // empty rather than occupied by the captured pawn.
+        let mut targets = *self.board.color_combined(!self.board.side_to_move());
+        if let Some(ep_square) = self.board.en_passant() {
+            // `en_passant()` is the square of the pawn that can be taken; the capturing
+            // pawn lands one rank beyond it.
+            targets |= chess::BitBoard::from_square(ep_square.uforward(self.board.side_to_move()));
+        }
+        let mut captures = MoveGen::new_legal(&self.board);
+        captures.set_iterator_mask(targets);
 
         for mv in captures {
-            // Check if it's a capture
-            if let Some(_) = self.board.piece_on(mv.get_dest()) {
-                // Use simplified SEE to separate good/bad captures
-                if self.see_simple(mv, 0) {
-                    self.good_captures.push(mv);
-                } else {
-                    self.bad_captures.push(mv);
-                }
+            // Use SEE to separate good/bad captures
+            if see(&self.board, mv, 0) {
+                self.good_captures.push(mv);
+            } else {
+                self.bad_captures.push(mv);
             }
         }
 
@@ -115,28 +140,6 @@ impl StagedMoveIterator {
         self.good_captures_idx = 0;
     }
 
-    fn see_simple(&self, mv: ChessMove, threshold: i16) -> bool {
-        // Simplified SEE for move ordering
-        const PIECE_VALUES: [i16; 6] = [100, 320, 330, 500, 900, 0];
-
-        let victim = self.board.piece_on(mv.get_dest());
-        let attacker = self.board.piece_on(mv.get_source());
-
-        if victim.is_none() {
-            return threshold <= 0;
-        }
-
-        if attacker.is_none() {
-            return false;
-        }
-
-        let victim_value = PIECE_VALUES[victim.unwrap().to_index()];
-        let attacker_value = PIECE_VALUES[attacker.unwrap().to_index()];
-
-        // Simple: capture is good if victim >= attacker
-        (victim_value - attacker_value) >= threshold
-    }
-
     fn generate_quiet(&mut self) {
         self.quiet_moves.clear();
 
@@ -180,6 +183,135 @@ impl StagedMoveIterator {
     }
 }
 
+/// All pieces (either color) that attack `target` given `occupied`, recomputed from
+/// scratch every call so sliding attacks automatically pick up X-ray attackers as
+/// pieces are removed from `occupied` during a `see` swap-off.
+///
+/// Free function (rather than a method on `StagedMoveIterator`) so `negamax::mod`'s
+/// pre-move SEE check can share it without needing a `StagedMoveIterator` instance.
+pub(super) fn attackers_to(board: &Board, target: Square, occupied: chess::BitBoard) -> chess::BitBoard {
+    use chess::Piece;
+
+    let knights = board.pieces(Piece::Knight) & occupied;
+    let bishops_queens = (board.pieces(Piece::Bishop) | board.pieces(Piece::Queen)) & occupied;
+    let rooks_queens = (board.pieces(Piece::Rook) | board.pieces(Piece::Queen)) & occupied;
+    let kings = board.pieces(Piece::King) & occupied;
+    let white_pawns = board.pieces(Piece::Pawn) & board.color_combined(chess::Color::White) & occupied;
+    let black_pawns = board.pieces(Piece::Pawn) & board.color_combined(chess::Color::Black) & occupied;
+
+    (chess::get_knight_moves(target) & knights)
+        | (chess::get_bishop_moves(target, occupied) & bishops_queens)
+        | (chess::get_rook_moves(target, occupied) & rooks_queens)
+        | (chess::get_king_moves(target) & kings)
+        // A white pawn attacks `target` iff `target` is where a black pawn standing
+        // on it would attack from -- the usual reversed-attack trick.
+        | (chess::get_pawn_attacks(target, chess::Color::Black, !chess::EMPTY) & white_pawns)
+        | (chess::get_pawn_attacks(target, chess::Color::White, !chess::EMPTY) & black_pawns)
+}
+
+/// The cheapest piece of `color` among `attackers`, if any, in MVV-LVA order
+/// (pawn, knight, bishop, rook, queen, king).
+pub(super) fn least_valuable_attacker(
+    board: &Board,
+    attackers: chess::BitBoard,
+    color: chess::Color,
+) -> Option<(Square, chess::Piece)> {
+    use chess::Piece;
+
+    const ORDER: [Piece; 6] = [
+        Piece::Pawn,
+        Piece::Knight,
+        Piece::Bishop,
+        Piece::Rook,
+        Piece::Queen,
+        Piece::King,
+    ];
+
+    let own_attackers = attackers & board.color_combined(color);
+    for &piece in ORDER.iter() {
+        let mut candidates = own_attackers & board.pieces(piece);
+        if let Some(square) = candidates.next() {
+            return Some((square, piece));
+        }
+    }
+    None
+}
+
+/// Static Exchange Evaluation: simulates the full swap-off of captures and recaptures
+/// on `mv.get_dest()` and returns whether the side making `mv` nets at least
+/// `threshold` material once the exchange is played out to its end, not just from the
+/// first trade. Promotions on the target square count as the promoted piece's value;
+/// a king capturing terminates the exchange there, since it can't itself be
+/// recaptured. En passant removes the captured pawn from the source's rank rather
+/// than the destination square, so its victim value is looked up there instead.
+///
+/// Free function, not a `StagedMoveIterator` method, so both move-ordering (this file)
+/// and the pre-move pruning check in `negamax::mod` (`search_eval`/`quiescence`) share
+/// one implementation instead of maintaining two copies that can drift apart.
+pub(super) fn see(board: &Board, mv: ChessMove, threshold: i16) -> bool {
+    const PIECE_VALUES: [i16; 6] = [100, 320, 330, 500, 900, 0]; // P, N, B, R, Q, K
+
+    let target = mv.get_dest();
+    let attacker_square = mv.get_source();
+
+    let Some(attacker) = board.piece_on(attacker_square) else {
+        return false;
+    };
+
+    let is_en_passant = attacker == chess::Piece::Pawn
+        && board.piece_on(target).is_none()
+        && attacker_square.get_file() != target.get_file();
+    let victim_value = if is_en_passant {
+        PIECE_VALUES[chess::Piece::Pawn.to_index()]
+    } else if let Some(victim) = board.piece_on(target) {
+        PIECE_VALUES[victim.to_index()]
+    } else {
+        // Not a capture: there's no exchange to simulate.
+        return threshold <= 0;
+    };
+
+    // The value `mv`'s own mover places on `target` once it lands -- the promoted
+    // piece's value if `mv` is a promotion, else just the mover's own value.
+    let mover_value = mv
+        .get_promotion()
+        .map_or(PIECE_VALUES[attacker.to_index()], |p| PIECE_VALUES[p.to_index()]);
+
+    let en_passant_captured_sq = Square::make_square(attacker_square.get_rank(), target.get_file());
+    let mut occupied = *board.combined() & !chess::BitBoard::from_square(attacker_square);
+    if is_en_passant {
+        occupied &= !chess::BitBoard::from_square(en_passant_captured_sq);
+    }
+    let mut side_to_move = !board.side_to_move(); // who can recapture on `target` next
+    let mut value_on_target = mover_value;
+
+    let mut gain = vec![victim_value];
+
+    loop {
+        let attackers = attackers_to(board, target, occupied);
+        let Some((square, piece)) = least_valuable_attacker(board, attackers, side_to_move) else {
+            break;
+        };
+
+        gain.push(value_on_target - gain[gain.len() - 1]);
+
+        // The king can't be recaptured, so the exchange stops the moment it captures,
+        // regardless of what still attacks `target`.
+        if piece == chess::Piece::King {
+            break;
+        }
+
+        occupied &= !chess::BitBoard::from_square(square);
+        value_on_target = PIECE_VALUES[piece.to_index()];
+        side_to_move = !side_to_move;
+    }
+
+    for d in (1..gain.len()).rev() {
+        gain[d - 1] = -((-gain[d - 1]).max(gain[d]));
+    }
+
+    gain[0] >= threshold
+}
+
 impl Iterator for StagedMoveIterator {
     type Item = ChessMove;
 
@@ -213,7 +345,11 @@ impl Iterator for StagedMoveIterator {
 
                         return Some(mv);
                     }
-                    self.stage = MoveStage::Killer1;
+                    self.stage = if self.captures_only {
+                        MoveStage::BadCaptures
+                    } else {
+                        MoveStage::Killer1
+                    };
                 }
 
                 MoveStage::Killer1 => {
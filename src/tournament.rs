@@ -0,0 +1,327 @@
+//! Multi-game testing on top of `arbiter::play_match`.
+//!
+//! `play_match` plays a single game between two engines; comparing engine versions needs
+//! hundreds of games across a pool of opening positions, from both colors, with a running
+//! tally and a statistical stopping rule. `run_gauntlet` plays one candidate engine against
+//! a set of named opponents (each opening played once per color, so neither side is favoured
+//! by the position), aggregates W/D/L standings and an Elo estimate with error bars, and can
+//! be paired with an `Sprt` to stop the match early once the result is statistically decided.
+
+use crate::arbiter::{play_match, GameResult, Pgn, TimeControl};
+use crate::engine::{EvaluateEngine, SearchEngine};
+
+/// One played game, kept alongside its PGN so the whole tournament can be written out as a
+/// single PGN collection afterwards.
+#[derive(Debug, Clone)]
+pub struct MatchRecord {
+    pub white: String,
+    pub black: String,
+    pub opening_fen: String,
+    pub result: GameResult,
+    pub pgn: Pgn,
+}
+
+/// Running W/D/L tally for one named engine across a tournament.
+#[derive(Debug, Clone)]
+pub struct EngineStanding {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl EngineStanding {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+
+    fn record(&mut self, result: GameResult, played_white: bool) {
+        match (result, played_white) {
+            (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => self.wins += 1,
+            (GameResult::Draw, _) => self.draws += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    /// Elo difference implied by this standing's record against its opponents, with a
+    /// 95%-confidence error margin.
+    pub fn elo(&self) -> (f64, f64) {
+        elo_from_results(self.wins, self.draws, self.losses)
+    }
+}
+
+/// Elo difference implied by a win/draw/loss record, with a 95% confidence error margin
+/// from the normal approximation of the score's standard error (the same style of figure
+/// engine-testing tools report next to a match result).
+pub fn elo_from_results(wins: u32, draws: u32, losses: u32) -> (f64, f64) {
+    let n = (wins + draws + losses) as f64;
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let score = ((wins as f64 + 0.5 * draws as f64) / n).clamp(1e-6, 1.0 - 1e-6);
+    let elo = -400.0 * (1.0 / score - 1.0).log10();
+
+    let win_p = wins as f64 / n;
+    let draw_p = draws as f64 / n;
+    let loss_p = losses as f64 / n;
+    let variance =
+        win_p * (1.0 - score).powi(2) + draw_p * (0.5 - score).powi(2) + loss_p * score.powi(2);
+    let std_err = (variance / n).sqrt();
+
+    let score_hi = (score + 1.96 * std_err).clamp(1e-6, 1.0 - 1e-6);
+    let margin = (-400.0 * (1.0 / score_hi - 1.0).log10() - elo).abs();
+
+    (elo, margin)
+}
+
+/// Outcome probabilities `(p_win, p_draw, p_loss)` implied by an Elo difference, under the
+/// same two-parameter (elo, draw_elo) logistic model fishtest-style SPRT calculators use.
+fn outcome_probabilities(elo: f64, draw_elo: f64) -> (f64, f64, f64) {
+    let p_win = 1.0 / (1.0 + 10f64.powf((draw_elo - elo) / 400.0));
+    let p_loss = 1.0 / (1.0 + 10f64.powf((draw_elo + elo) / 400.0));
+    let p_draw = 1.0 - p_win - p_loss;
+    (p_win, p_draw, p_loss)
+}
+
+/// Which hypothesis an `Sprt` has settled on, once its log-likelihood ratio has crossed a
+/// boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// LLR crossed `log(beta / (1 - alpha))`: accept H0 (candidate is not stronger than elo0).
+    AcceptH0,
+    /// LLR crossed `log((1 - beta) / alpha)`: accept H1 (candidate is at least as strong as elo1).
+    AcceptH1,
+}
+
+/// Sequential Probability Ratio Test for engine-vs-engine matches, comparing the hypotheses
+/// "candidate is `elo0` Elo stronger than the baseline" (H0) against "`elo1` Elo stronger"
+/// (H1). Maintains the log-likelihood ratio game by game under a trinomial (win/draw/loss)
+/// model and stops as soon as it crosses a decision boundary set by the `alpha`/`beta` error
+/// rates.
+#[derive(Debug, Clone)]
+pub struct Sprt {
+    elo0: f64,
+    elo1: f64,
+    draw_elo: f64,
+    alpha: f64,
+    beta: f64,
+    llr: f64,
+}
+
+impl Sprt {
+    /// `draw_elo` is fixed at 200, a typical observed value for engines of similar strength;
+    /// see `outcome_probabilities`.
+    pub fn new(elo0: f64, elo1: f64, alpha: f64, beta: f64) -> Self {
+        Self {
+            elo0,
+            elo1,
+            draw_elo: 200.0,
+            alpha,
+            beta,
+            llr: 0.0,
+        }
+    }
+
+    /// Folds one game's result into the running LLR, from the candidate's point of view.
+    pub fn record(&mut self, result: GameResult, candidate_played_white: bool) {
+        let (p0_win, p0_draw, p0_loss) = outcome_probabilities(self.elo0, self.draw_elo);
+        let (p1_win, p1_draw, p1_loss) = outcome_probabilities(self.elo1, self.draw_elo);
+
+        let candidate_won = (result == GameResult::WhiteWins) == candidate_played_white;
+        let (p0, p1) = match result {
+            GameResult::Draw => (p0_draw, p1_draw),
+            _ if candidate_won => (p0_win, p1_win),
+            _ => (p0_loss, p1_loss),
+        };
+
+        self.llr += (p1 / p0).ln();
+    }
+
+    pub fn llr(&self) -> f64 {
+        self.llr
+    }
+
+    /// `None` means the test should continue; otherwise the LLR has crossed a boundary.
+    pub fn decision(&self) -> Option<SprtDecision> {
+        let lower_bound = (self.beta / (1.0 - self.alpha)).ln();
+        let upper_bound = ((1.0 - self.beta) / self.alpha).ln();
+
+        if self.llr <= lower_bound {
+            Some(SprtDecision::AcceptH0)
+        } else if self.llr >= upper_bound {
+            Some(SprtDecision::AcceptH1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Every game played plus the final standings of a gauntlet.
+#[derive(Debug, Clone)]
+pub struct TournamentResult {
+    pub games: Vec<MatchRecord>,
+    pub standings: Vec<EngineStanding>,
+}
+
+impl TournamentResult {
+    /// A plain-text results table: one row per engine with its W/D/L tally and the Elo
+    /// difference (with error margin) that record implies.
+    pub fn results_table(&self) -> String {
+        let mut table = String::from("Engine               W    D    L   Elo\n");
+        for standing in &self.standings {
+            let (elo, margin) = standing.elo();
+            table.push_str(&format!(
+                "{:<20} {:>4} {:>4} {:>4}   {:+.1} +/- {:.1}\n",
+                standing.name, standing.wins, standing.draws, standing.losses, elo, margin
+            ));
+        }
+        table
+    }
+
+    /// Concatenates every played game's PGN into one collection, ready to write to a .pgn file.
+    pub fn combined_pgn(&self) -> String {
+        self.games
+            .iter()
+            .map(|game| game.pgn.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Plays `candidate` as a gauntlet against each of `opponents`, across every opening in
+/// `openings`, once from each color (a color-balanced pair per opening), under `time_control`
+/// (`None` for untimed search). If `sprt` is supplied, every game result also updates it and
+/// the gauntlet stops as soon as it reaches a decision, even if openings/opponents remain.
+///
+/// This is a gauntlet rather than a full round robin: only candidate-vs-opponent games are
+/// played, not opponent-vs-opponent.
+pub fn run_gauntlet<E: EvaluateEngine>(
+    candidate_name: &str,
+    candidate: &mut Box<dyn SearchEngine<E>>,
+    opponents: &mut [(String, Box<dyn SearchEngine<E>>)],
+    openings: &[String],
+    max_moves: usize,
+    time_control: Option<TimeControl>,
+    mut sprt: Option<Sprt>,
+) -> (TournamentResult, Option<Sprt>) {
+    let mut standings = vec![EngineStanding::new(candidate_name)];
+    standings.extend(opponents.iter().map(|(name, _)| EngineStanding::new(name)));
+
+    let mut games = Vec::new();
+
+    'gauntlet: for (opp_idx, (opp_name, opponent)) in opponents.iter_mut().enumerate() {
+        for fen in openings {
+            for candidate_plays_white in [true, false] {
+                let outcome = if candidate_plays_white {
+                    play_match::<E>(candidate, opponent, fen, Some(max_moves), time_control, None, None)
+                } else {
+                    play_match::<E>(opponent, candidate, fen, Some(max_moves), time_control, None, None)
+                };
+
+                // An unplayable opening (bad FEN) shouldn't abort the whole gauntlet.
+                let (result, _state, pgn, _move_times, _samples) = match outcome {
+                    Ok(played) => played,
+                    Err(_) => continue,
+                };
+
+                standings[0].record(result, candidate_plays_white);
+                standings[opp_idx + 1].record(result, !candidate_plays_white);
+
+                games.push(MatchRecord {
+                    white: if candidate_plays_white {
+                        candidate_name.to_string()
+                    } else {
+                        opp_name.clone()
+                    },
+                    black: if candidate_plays_white {
+                        opp_name.clone()
+                    } else {
+                        candidate_name.to_string()
+                    },
+                    opening_fen: fen.clone(),
+                    result,
+                    pgn,
+                });
+
+                if let Some(sprt) = sprt.as_mut() {
+                    sprt.record(result, candidate_plays_white);
+                    if sprt.decision().is_some() {
+                        break 'gauntlet;
+                    }
+                }
+            }
+        }
+    }
+
+    (TournamentResult { games, standings }, sprt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elo_from_results_zero_games_is_the_only_zero_case() {
+        assert_eq!(elo_from_results(0, 0, 0), (0.0, 0.0));
+        // An even record away from n == 0 lands at 0 Elo too, but carries a nonzero margin.
+        let (elo, margin) = elo_from_results(50, 0, 50);
+        assert!(elo.abs() < 1e-9);
+        assert!(margin > 0.0);
+    }
+
+    #[test]
+    fn test_elo_from_results_pinned_value() {
+        // +50/=0/-50: score 0.5 both sides of the interval, so Elo comes out at (about) 0
+        // with a margin of about 69 Elo at the 95% confidence level used here.
+        let (elo, margin) = elo_from_results(50, 0, 50);
+        assert!(elo.abs() < 1e-9, "elo was {elo}");
+        assert!((margin - 68.99005236157632).abs() < 1e-6, "margin was {margin}");
+    }
+
+    #[test]
+    fn test_sprt_record_credits_the_candidate_regardless_of_color() {
+        // A win is a win whether the candidate played it as White or Black -- the LLR
+        // contribution must be identical either way.
+        let mut as_white = Sprt::new(0.0, 10.0, 0.05, 0.05);
+        as_white.record(GameResult::WhiteWins, true);
+
+        let mut as_black = Sprt::new(0.0, 10.0, 0.05, 0.05);
+        as_black.record(GameResult::BlackWins, false);
+
+        assert!((as_white.llr() - as_black.llr()).abs() < 1e-12);
+        assert!(as_white.llr() > 0.0, "a candidate win should push the LLR toward H1");
+
+        // Conversely, White winning while the candidate played Black is a loss for it.
+        let mut candidate_lost = Sprt::new(0.0, 10.0, 0.05, 0.05);
+        candidate_lost.record(GameResult::WhiteWins, false);
+        assert!(candidate_lost.llr() < 0.0, "a candidate loss should push the LLR toward H0");
+    }
+
+    #[test]
+    fn test_sprt_decision_crosses_known_boundaries() {
+        // elo0=0, elo1=10, alpha=beta=0.05 puts the boundaries at +/-ln(19) =~ +/-2.944.
+        // 68 straight candidate wins (each contributing ~0.0434 to the LLR) cross the
+        // upper bound; 67 straight candidate losses (each ~-0.0440) cross the lower one.
+        let mut wins = Sprt::new(0.0, 10.0, 0.05, 0.05);
+        for _ in 0..67 {
+            wins.record(GameResult::WhiteWins, true);
+        }
+        assert_eq!(wins.decision(), None, "67 wins shouldn't have crossed the boundary yet");
+        wins.record(GameResult::WhiteWins, true);
+        assert_eq!(wins.decision(), Some(SprtDecision::AcceptH1));
+
+        let mut losses = Sprt::new(0.0, 10.0, 0.05, 0.05);
+        for _ in 0..66 {
+            losses.record(GameResult::BlackWins, true);
+        }
+        assert_eq!(losses.decision(), None, "66 losses shouldn't have crossed the boundary yet");
+        losses.record(GameResult::BlackWins, true);
+        assert_eq!(losses.decision(), Some(SprtDecision::AcceptH0));
+    }
+}
@@ -2,14 +2,16 @@ use crate::engine::{EvaluateEngine, GameState, SearchEngine, TimeInfo};
 
 use chess::{Board, ChessMove, Piece, Square};
 use log::{debug, error};
-use vampirc_uci::{UciFen, UciMessage, UciMove, UciPiece, UciSquare, UciTimeControl, parse_one};
+use vampirc_uci::{
+    UciFen, UciMessage, UciMove, UciOptionConfig, UciPiece, UciSquare, UciTimeControl, parse_one,
+};
 
 use std::{
     io::{self, BufRead},
     str::FromStr,
 };
 
-pub fn uci_loop<E: EvaluateEngine, S: SearchEngine<E>>(engine: &mut S) -> () {
+pub fn uci_loop<E: EvaluateEngine, S: SearchEngine<E> + ?Sized>(engine: &mut S) -> () {
     let stdin = io::stdin();
 
     let mut game_state = GameState::default();
@@ -25,10 +27,40 @@ pub fn uci_loop<E: EvaluateEngine, S: SearchEngine<E>>(engine: &mut S) -> () {
 
                 println!("{}", name);
                 println!("{}", author);
+                println!(
+                    "{}",
+                    UciMessage::Option(UciOptionConfig::Spin {
+                        name: "Hash".to_string(),
+                        default: Some(256),
+                        min: Some(1),
+                        max: Some(65536),
+                    })
+                );
+                println!(
+                    "{}",
+                    UciMessage::Option(UciOptionConfig::Spin {
+                        name: "Threads".to_string(),
+                        default: Some(1),
+                        min: Some(1),
+                        max: Some(256),
+                    })
+                );
                 println!("{}", UciMessage::UciOk);
             }
             UciMessage::IsReady => println!("{}", UciMessage::ReadyOk),
 
+            UciMessage::SetOption { name, value } => match name.as_str() {
+                "Hash" => match value.as_deref().and_then(|v| v.parse::<usize>().ok()) {
+                    Some(megabytes) => engine.set_hash_size_mb(megabytes),
+                    None => error!("SetOption Hash with missing/invalid value: {:?}", value),
+                },
+                "Threads" => match value.as_deref().and_then(|v| v.parse::<usize>().ok()) {
+                    Some(threads) => engine.set_threads(threads),
+                    None => error!("SetOption Threads with missing/invalid value: {:?}", value),
+                },
+                _ => error!("Unimplemented option {}", name),
+            },
+
             UciMessage::Position {
                 startpos,
                 fen,
@@ -54,41 +86,62 @@ pub fn uci_loop<E: EvaluateEngine, S: SearchEngine<E>>(engine: &mut S) -> () {
             }
             UciMessage::Go {
                 time_control,
-                search_control: _,
+                search_control,
             } => {
-                // TODO: Implement search control parsing ?
-                let time_control = if let Some(tc) = time_control {
-                    match tc {
-                        UciTimeControl::Infinite => None,
-                        UciTimeControl::TimeLeft {
-                            white_time,
-                            black_time,
-                            white_increment,
-                            black_increment,
-                            moves_to_go,
-                        } => Some(TimeInfo {
-                            white_time,
-                            black_time,
-                            white_increment,
-                            black_increment,
-                            moves_to_go,
-                            move_time: None,
-                        }),
-                        UciTimeControl::MoveTime(move_time) => Some(TimeInfo {
-                            move_time: Some(move_time),
-                            white_time: None,
-                            black_time: None,
-                            white_increment: None,
-                            black_increment: None,
-                            moves_to_go: None,
-                        }),
-                        UciTimeControl::Ponder => None,
-                    }
-                } else {
-                    None
+                let mut time_info = match time_control {
+                    Some(UciTimeControl::TimeLeft {
+                        white_time,
+                        black_time,
+                        white_increment,
+                        black_increment,
+                        moves_to_go,
+                    }) => TimeInfo {
+                        white_time,
+                        black_time,
+                        white_increment,
+                        black_increment,
+                        moves_to_go,
+                        move_time: None,
+                        max_depth: None,
+                        max_nodes: None,
+                        search_moves: None,
+                    },
+                    Some(UciTimeControl::MoveTime(move_time)) => TimeInfo {
+                        move_time: Some(move_time),
+                        white_time: None,
+                        black_time: None,
+                        white_increment: None,
+                        black_increment: None,
+                        moves_to_go: None,
+                        max_depth: None,
+                        max_nodes: None,
+                        search_moves: None,
+                    },
+                    // `Infinite`, `Ponder`, and no time control at all all mean "no clock",
+                    // left for `search_control` below to bound instead, if at all.
+                    Some(UciTimeControl::Infinite) | Some(UciTimeControl::Ponder) | None => TimeInfo {
+                        move_time: None,
+                        white_time: None,
+                        black_time: None,
+                        white_increment: None,
+                        black_increment: None,
+                        moves_to_go: None,
+                        max_depth: None,
+                        max_nodes: None,
+                        search_moves: None,
+                    },
                 };
 
-                let best_move = engine.next_move(game_state.clone(), &time_control);
+                if let Some(sc) = search_control {
+                    time_info.max_depth = sc.depth.map(|d| d as u16);
+                    time_info.max_nodes = sc.nodes;
+                    if !sc.search_moves.is_empty() {
+                        time_info.search_moves =
+                            Some(sc.search_moves.into_iter().map(from_uci_move).collect());
+                    }
+                }
+
+                let best_move = engine.next_move(game_state.clone(), time_info);
                 match best_move {
                     Some(mv) => {
                         let uci_move = into_uci_move(mv);
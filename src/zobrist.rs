@@ -0,0 +1,273 @@
+//! Incrementally-updated Zobrist hashing for `GameState`.
+//!
+//! Precomputes a random 64-bit key for every (color, piece, square), every castling
+//! right, every en-passant file, and one key for side-to-move. `hash` builds a position
+//! key from scratch (used to seed a fresh `GameState`); `update` moves an existing key
+//! from one board to the next by XOR-ing out only the keys for features that changed
+//! (the moved piece's source/dest squares, a captured piece, the castling rook on O-O/
+//! O-O-O, the captured pawn on en passant, any castling-rights/en-passant-file change,
+//! and the side-to-move flip) instead of recomputing the whole hash.
+
+use chess::{Board, CastleRights, ChessMove, Color, Piece, Square, ALL_SQUARES};
+
+struct ZobristKeys {
+    piece_square: [[[u64; 64]; 6]; 2],
+    castling: [u64; 4],
+    en_passant_file: [u64; 8],
+    side_to_move: u64,
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+fn keys() -> &'static ZobristKeys {
+    static KEYS: std::sync::OnceLock<ZobristKeys> = std::sync::OnceLock::new();
+    KEYS.get_or_init(|| {
+        // Fixed seed: hashes only need to be stable within a single run (TT keys,
+        // repetition counts), not across builds.
+        let mut state = 0x5EED_C0FFEE_u64;
+
+        let mut piece_square = [[[0u64; 64]; 6]; 2];
+        for color in piece_square.iter_mut() {
+            for piece in color.iter_mut() {
+                for square in piece.iter_mut() {
+                    *square = splitmix64(&mut state);
+                }
+            }
+        }
+
+        let mut castling = [0u64; 4];
+        for key in castling.iter_mut() {
+            *key = splitmix64(&mut state);
+        }
+
+        let mut en_passant_file = [0u64; 8];
+        for key in en_passant_file.iter_mut() {
+            *key = splitmix64(&mut state);
+        }
+
+        ZobristKeys {
+            piece_square,
+            castling,
+            en_passant_file,
+            side_to_move: splitmix64(&mut state),
+        }
+    })
+}
+
+fn piece_index(piece: Piece) -> usize {
+    match piece {
+        Piece::Pawn => 0,
+        Piece::Knight => 1,
+        Piece::Bishop => 2,
+        Piece::Rook => 3,
+        Piece::Queen => 4,
+        Piece::King => 5,
+    }
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// `(kingside, queenside)` availability bits for a single `CastleRights` value.
+fn castling_bits(rights: CastleRights) -> (bool, bool) {
+    match rights {
+        CastleRights::Both => (true, true),
+        CastleRights::KingSide => (true, false),
+        CastleRights::QueenSide => (false, true),
+        CastleRights::NoRights => (false, false),
+    }
+}
+
+fn piece_square_key(color: Color, piece: Piece, square: Square) -> u64 {
+    keys().piece_square[color_index(color)][piece_index(piece)][square.to_index()]
+}
+
+/// Full from-scratch Zobrist hash of `board`. Used to seed a fresh `GameState` and as
+/// the ground truth `update`'s incremental result is checked against in tests.
+pub fn hash(board: &Board) -> u64 {
+    let k = keys();
+    let mut hash = 0u64;
+
+    for square in ALL_SQUARES.iter() {
+        if let Some(piece) = board.piece_on(*square) {
+            let color = board.color_on(*square).expect("piece without color");
+            hash ^= piece_square_key(color, piece, *square);
+        }
+    }
+
+    for (i, &color) in [Color::White, Color::Black].iter().enumerate() {
+        let (kingside, queenside) = castling_bits(board.castle_rights(color));
+        if kingside {
+            hash ^= k.castling[i * 2];
+        }
+        if queenside {
+            hash ^= k.castling[i * 2 + 1];
+        }
+    }
+
+    if let Some(ep) = board.en_passant() {
+        hash ^= k.en_passant_file[ep.get_file().to_index()];
+    }
+
+    if board.side_to_move() == Color::Black {
+        hash ^= k.side_to_move;
+    }
+
+    hash
+}
+
+/// The rook's `(from, to)` squares if `source -> dest` is a castling king move, else
+/// `None`. `pub(crate)` so `GameState::undo_last_move` can reuse the same detection
+/// when reversing a castle, instead of re-deriving it.
+pub(crate) fn castling_rook_move(source: Square, dest: Square) -> Option<(Square, Square)> {
+    match (source, dest) {
+        (Square::E1, Square::G1) => Some((Square::H1, Square::F1)),
+        (Square::E1, Square::C1) => Some((Square::A1, Square::D1)),
+        (Square::E8, Square::G8) => Some((Square::H8, Square::F8)),
+        (Square::E8, Square::C8) => Some((Square::A8, Square::D8)),
+        _ => None,
+    }
+}
+
+/// Incrementally moves `hash` from `before` to `after` (the result of playing `mv` on
+/// `before`), XOR-ing out only the keys for features that actually changed rather than
+/// recomputing the hash from scratch.
+pub fn update(hash: u64, before: &Board, mv: ChessMove, after: &Board) -> u64 {
+    let k = keys();
+    let mut hash = hash;
+
+    let side = before.side_to_move();
+    let source = mv.get_source();
+    let dest = mv.get_dest();
+    let moved_piece = before.piece_on(source).expect("move source must hold a piece");
+
+    // The moving piece leaves its source square.
+    hash ^= piece_square_key(side, moved_piece, source);
+
+    // A regular capture: whatever stood on the destination square disappears.
+    if let Some(captured) = before.piece_on(dest) {
+        hash ^= piece_square_key(!side, captured, dest);
+    } else if moved_piece == Piece::Pawn && source.get_file() != dest.get_file() {
+        // En passant: the captured pawn sits on the source's rank, dest's file.
+        let captured_sq = Square::make_square(source.get_rank(), dest.get_file());
+        hash ^= piece_square_key(!side, Piece::Pawn, captured_sq);
+    }
+
+    // The moved piece (or its promoted form) appears on the destination square.
+    let landing_piece = mv.get_promotion().unwrap_or(moved_piece);
+    hash ^= piece_square_key(side, landing_piece, dest);
+
+    // Castling also moves the rook.
+    if moved_piece == Piece::King {
+        if let Some((rook_from, rook_to)) = castling_rook_move(source, dest) {
+            hash ^= piece_square_key(side, Piece::Rook, rook_from);
+            hash ^= piece_square_key(side, Piece::Rook, rook_to);
+        }
+    }
+
+    // Castling rights, whichever squares changed them (king/rook moves or captures).
+    for &color in &[Color::White, Color::Black] {
+        let (before_k, before_q) = castling_bits(before.castle_rights(color));
+        let (after_k, after_q) = castling_bits(after.castle_rights(color));
+        if before_k != after_k {
+            hash ^= k.castling[color_index(color) * 2];
+        }
+        if before_q != after_q {
+            hash ^= k.castling[color_index(color) * 2 + 1];
+        }
+    }
+
+    // En-passant file, cleared unconditionally and re-set if the new position allows it.
+    if let Some(sq) = before.en_passant() {
+        hash ^= k.en_passant_file[sq.get_file().to_index()];
+    }
+    if let Some(sq) = after.en_passant() {
+        hash ^= k.en_passant_file[sq.get_file().to_index()];
+    }
+
+    // Side to move always flips.
+    hash ^= k.side_to_move;
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess::MoveGen;
+    use std::str::FromStr;
+
+    fn assert_matches_from_scratch(hash: u64, board: &Board) {
+        assert_eq!(hash, super::hash(board), "incremental hash diverged from from-scratch recomputation");
+    }
+
+    #[test]
+    fn test_incremental_matches_from_scratch_over_random_moves() {
+        let mut board = Board::default();
+        let mut hash = super::hash(&board);
+
+        let mut seed = 777u64;
+        for _ in 0..60 {
+            let moves: Vec<_> = MoveGen::new_legal(&board).collect();
+            if moves.is_empty() {
+                break;
+            }
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let mv = moves[(seed as usize) % moves.len()];
+            let after = board.make_move_new(mv);
+            hash = update(hash, &board, mv, &after);
+            assert_matches_from_scratch(hash, &after);
+            board = after;
+        }
+    }
+
+    #[test]
+    fn test_incremental_handles_castling() {
+        let board =
+            Board::from_str("rnbqk2r/pppp1ppp/5n2/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4").unwrap();
+        let mut hash = super::hash(&board);
+
+        let castle = MoveGen::new_legal(&board)
+            .find(|m| m.get_source() == Square::E1 && m.get_dest() == Square::G1)
+            .unwrap();
+        let after = board.make_move_new(castle);
+        hash = update(hash, &board, castle, &after);
+        assert_matches_from_scratch(hash, &after);
+    }
+
+    #[test]
+    fn test_incremental_handles_promotion() {
+        let board = Board::from_str("8/P6k/8/8/8/8/7p/7K w - - 0 1").unwrap();
+        let mut hash = super::hash(&board);
+
+        let promo = MoveGen::new_legal(&board)
+            .find(|m| m.get_promotion() == Some(Piece::Queen))
+            .unwrap();
+        let after = board.make_move_new(promo);
+        hash = update(hash, &board, promo, &after);
+        assert_matches_from_scratch(hash, &after);
+    }
+
+    #[test]
+    fn test_incremental_handles_en_passant() {
+        let board = Board::from_str("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2").unwrap();
+        let mut hash = super::hash(&board);
+
+        let ep = MoveGen::new_legal(&board)
+            .find(|m| m.get_source() == Square::E5 && m.get_dest() == Square::D6)
+            .unwrap();
+        let after = board.make_move_new(ep);
+        hash = update(hash, &board, ep, &after);
+        assert_matches_from_scratch(hash, &after);
+    }
+}